@@ -0,0 +1,57 @@
+use crate::Config;
+use color_eyre::eyre::Result;
+use latest_version::{Client, Coordinates, Requirement, Versions};
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Advisories {
+    /// Show the known advisories of the current and the reported versions.
+    Show,
+    /// Also skip versions with known advisories when looking for the latest versions.
+    Exclude,
+}
+
+/// Finds the latest version for each requirement, skipping versions with known advisories if configured.
+pub(crate) async fn latest_versions(
+    client: &Client,
+    config: &Config,
+    coordinates: &Coordinates,
+    versions: Versions,
+    requirements: Vec<Requirement>,
+) -> Result<(Vec<(Requirement, Option<String>)>, Option<String>)> {
+    let mut versions = versions;
+    if config.advisories != Some(Advisories::Exclude) {
+        return Ok(versions.latest_versions_and_overall(config.include_pre_releases, requirements));
+    }
+
+    // Only the latest versions are checked, if one is affected, the next best version is checked instead.
+    let mut unaffected = HashSet::new();
+    loop {
+        let (latest, overall) = versions
+            .clone()
+            .latest_versions_and_overall(config.include_pre_releases, requirements.clone());
+
+        let mut affected = None;
+        for version in latest
+            .iter()
+            .filter_map(|(_, v)| v.as_deref())
+            .chain(overall.as_deref())
+        {
+            if unaffected.contains(version) {
+                continue;
+            }
+            let details = client.query_version_details(coordinates, version).await?;
+            if details.advisories.is_empty() {
+                let _ = unaffected.insert(version.to_string());
+            } else {
+                affected = Some(version.to_string());
+                break;
+            }
+        }
+
+        match affected {
+            Some(version) => versions.remove(&version),
+            None => return Ok((latest, overall)),
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{Coordinates, Credentials, DependencyGraph, Release, VersionDetails, Versions};
+use reqwest::Url;
+use serde_json::Value;
+use std::{collections::HashMap, time::Duration};
+
+/// A client to query deps.dev for the versions of a package.
+#[derive(Debug, Clone)]
+pub struct Client {
+    client: reqwest::Client,
+    credentials: Credentials,
+    registries: HashMap<String, Url>,
+}
+
+impl Client {
+    /// Creates a new client that authenticates requests with the given credentials.
+    pub fn new(credentials: Credentials) -> reqwest::Result<Self> {
+        static APP_USER_AGENT: &str =
+            concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+        let client = reqwest::Client::builder()
+            .user_agent(APP_USER_AGENT)
+            .gzip(true)
+            .timeout(Duration::from_secs(10))
+            .tcp_keepalive(Some(Duration::from_secs(60)))
+            .use_rustls_tls()
+            .build()?;
+
+        Ok(Self {
+            client,
+            credentials,
+            registries: HashMap::new(),
+        })
+    }
+
+    /// Queries a deps.dev compatible API at `url` instead of deps.dev for all packages of a system.
+    ///
+    /// The package path is appended to the url,
+    /// the default for `maven` would be `https://deps.dev/_/s/maven`.
+    pub fn with_registry(mut self, system: impl Into<String>, url: Url) -> Self {
+        let _ = self.registries.insert(system.into(), url);
+        self
+    }
+
+    /// Fetches all published versions of the package.
+    pub async fn query_versions(&self, coordinates: &Coordinates) -> reqwest::Result<Versions> {
+        let url = self.versions_url(coordinates);
+        let request = self
+            .credentials
+            .authorize(self.client.get(url.clone()), &url);
+        let response = request.send().await?.json::<Value>().await?;
+        let versions = response
+            .get("versions")
+            .and_then(|v| v.as_array())
+            .map_or_else(Versions::default, |v| {
+                v.iter().filter_map(Release::from_json).collect()
+            });
+        Ok(versions)
+    }
+
+    /// Fetches the details of a single published version of the package.
+    pub async fn query_version_details(
+        &self,
+        coordinates: &Coordinates,
+        version: &str,
+    ) -> reqwest::Result<VersionDetails> {
+        let url = self.version_url(coordinates, version);
+        let request = self
+            .credentials
+            .authorize(self.client.get(url.clone()), &url);
+        let response = request.send().await?.json::<Value>().await?;
+        Ok(VersionDetails::from_json(version, &response))
+    }
+
+    /// Fetches the resolved dependency graph of a single published version of the package.
+    pub async fn query_dependencies(
+        &self,
+        coordinates: &Coordinates,
+        version: &str,
+    ) -> reqwest::Result<DependencyGraph> {
+        let mut url = self.version_url(coordinates, version);
+        url.path_segments_mut()
+            .expect("registry urls can be a base")
+            .push("dependencies");
+        let request = self
+            .credentials
+            .authorize(self.client.get(url.clone()), &url);
+        let response = request.send().await?.json::<Value>().await?;
+        Ok(DependencyGraph::from_json(&response))
+    }
+
+    fn versions_url(&self, coordinates: &Coordinates) -> Url {
+        let mut url = self.package_url(coordinates);
+        url.path_segments_mut()
+            .expect("registry urls can be a base")
+            .push("versions");
+        url
+    }
+
+    fn version_url(&self, coordinates: &Coordinates, version: &str) -> Url {
+        let mut url = self.package_url(coordinates);
+        url.path_segments_mut()
+            .expect("registry urls can be a base")
+            .extend(["v", version]);
+        url
+    }
+
+    fn package_url(&self, coordinates: &Coordinates) -> Url {
+        let system = coordinates.system_slug();
+        let mut url = match self.registries.get(system) {
+            Some(url) => url.clone(),
+            None => {
+                let mut url = Url::parse("https://deps.dev/_/s").expect("this is a valid url");
+                url.path_segments_mut()
+                    .expect("url can be a base")
+                    .push(system);
+                url
+            }
+        };
+        url.path_segments_mut()
+            .expect("registry urls can be a base")
+            .pop_if_empty()
+            .extend(["p", &coordinates.package_slug()]);
+        url
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_versions_url() {
+        let client = Client::new(Credentials::default()).unwrap();
+        let url = client.versions_url(&Coordinates::Npm {
+            scope: Some("types".into()),
+            package: "neo4j".into(),
+        });
+        assert_eq!(
+            url.as_str(),
+            "https://deps.dev/_/s/npm/p/@types%2Fneo4j/versions"
+        );
+    }
+
+    #[test]
+    fn version_url() {
+        let client = Client::new(Credentials::default()).unwrap();
+        let url = client.version_url(
+            &Coordinates::Maven {
+                group_id: "com.google.guava".into(),
+                artifact_id: "guava".into(),
+            },
+            "31.1-jre",
+        );
+        assert_eq!(
+            url.as_str(),
+            "https://deps.dev/_/s/maven/p/com.google.guava:guava/v/31.1-jre"
+        );
+    }
+
+    #[test]
+    fn registry_versions_url() {
+        let client = Client::new(Credentials::default()).unwrap().with_registry(
+            "cargo",
+            Url::parse("https://mirror.example.com/deps/cargo/").unwrap(),
+        );
+        let url = client.versions_url(&Coordinates::Cargo("serde".into()));
+        assert_eq!(
+            url.as_str(),
+            "https://mirror.example.com/deps/cargo/p/serde/versions"
+        );
+    }
+}
@@ -0,0 +1,121 @@
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use reqwest::Url;
+use serde::Deserialize;
+use std::{collections::HashMap, env, fs, path::PathBuf};
+
+static FILE_NAME: &str = concat!(env!("CARGO_PKG_NAME"), ".toml");
+
+/// Settings from `latest-version.toml`.
+///
+/// The file is looked up at `$LATEST_VERSION_CONFIG`, then in the current directory
+/// and its parents, and finally in the `latest-version` directory of the user config dir.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct ConfigFile {
+    pub(crate) include_pre_releases: bool,
+    pub(crate) include_yanked: bool,
+    pub(crate) registries: HashMap<String, Registry>,
+    pub(crate) groups: HashMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Registry {
+    pub(crate) url: Url,
+}
+
+impl ConfigFile {
+    pub(crate) fn load() -> Result<Self> {
+        match config_file() {
+            Some(file) => {
+                let content = fs::read_to_string(&file)
+                    .wrap_err_with(|| format!("Could not read {}", file.display()))?;
+                Self::parse(&content)
+                    .wrap_err_with(|| format!("Could not parse {}", file.display()))
+            }
+            None => Ok(Self::default()),
+        }
+    }
+
+    fn parse(content: &str) -> Result<Self> {
+        let config = toml::from_str::<Self>(content)?;
+        if let Some(system) = config
+            .registries
+            .keys()
+            .find(|system| !matches!(system.as_str(), "maven" | "cargo" | "npm" | "go"))
+        {
+            return Err(eyre!("Unknown system `{}` in registries", system));
+        }
+        Ok(config)
+    }
+}
+
+fn config_file() -> Option<PathBuf> {
+    if let Some(file) = env::var_os("LATEST_VERSION_CONFIG") {
+        return Some(PathBuf::from(file));
+    }
+
+    let project_file = env::current_dir().ok().and_then(|dir| {
+        dir.ancestors()
+            .map(|dir| dir.join(FILE_NAME))
+            .find(|file| file.is_file())
+    });
+
+    project_file.or_else(|| {
+        dirs::config_dir()
+            .map(|dir| dir.join(env!("CARGO_PKG_NAME")).join(FILE_NAME))
+            .filter(|file| file.is_file())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_config() {
+        assert_eq!(ConfigFile::parse("").unwrap(), ConfigFile::default());
+    }
+
+    #[test]
+    fn full_config() {
+        let config = ConfigFile::parse(
+            r#"
+            include_pre_releases = true
+            include_yanked = true
+
+            [registries.maven]
+            url = "https://artifactory.example.com/deps/maven"
+
+            [groups]
+            backend = ["cargo:tokio:^1", "cargo:serde"]
+            "#,
+        )
+        .unwrap();
+
+        assert!(config.include_pre_releases);
+        assert!(config.include_yanked);
+        assert_eq!(
+            config.registries["maven"].url.as_str(),
+            "https://artifactory.example.com/deps/maven"
+        );
+        assert_eq!(config.groups["backend"], ["cargo:tokio:^1", "cargo:serde"]);
+    }
+
+    #[test]
+    fn unknown_registry_system() {
+        let err = ConfigFile::parse(
+            r#"
+            [registries.pypi]
+            url = "https://example.com"
+            "#,
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Unknown system `pypi` in registries");
+    }
+
+    #[test]
+    fn unknown_field() {
+        assert!(ConfigFile::parse("include_prereleases = true").is_err());
+    }
+}
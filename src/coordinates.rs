@@ -0,0 +1,545 @@
+use crate::{Requirement, VersionFilter, VersionScheme};
+use std::{borrow::Cow, fmt::Display, str::FromStr, time::Duration};
+
+/// The coordinates of a package and the requirements to check its versions against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionCheck {
+    pub coordinates: Coordinates,
+    pub versions: Vec<Requirement>,
+    /// The version that is currently in use, e.g. the one declared in a manifest.
+    pub current: Option<String>,
+    /// Which versions are considered at all.
+    pub filter: VersionFilter,
+    /// How the versions and requirements are read.
+    pub scheme: VersionScheme,
+    /// Only versions released within this duration are considered.
+    pub released_within: Option<Duration>,
+    /// The name the package is declared as, e.g. the alias in a version catalog, if that is not the package itself.
+    pub name: Option<String>,
+}
+
+/// A package in one of the systems that deps.dev knows about.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Coordinates {
+    /// A Maven artifact, `group_id:artifact_id`.
+    Maven {
+        /// e.g. `com.google.guava`
+        group_id: String,
+        /// e.g. `guava`
+        artifact_id: String,
+    },
+    /// A crate on crates.io.
+    Cargo(String),
+    /// An npm package, `@scope/package` or just `package`.
+    Npm {
+        /// The scope without the `@`, e.g. `types` for `@types/node`
+        scope: Option<String>,
+        /// The package name without the scope
+        package: String,
+    },
+    /// A go module on GitHub, `github.com/user/module`.
+    Go {
+        /// The user or organization on GitHub
+        user: String,
+        /// The repository of the module
+        module: String,
+    },
+    /// Any other go module, given by its full path, e.g. `golang.org/x/net`.
+    AnyGo(String),
+}
+
+impl Coordinates {
+    /// The name of the system on deps.dev.
+    pub fn system_slug(&self) -> &str {
+        match self {
+            Coordinates::Maven { .. } => "maven",
+            Coordinates::Cargo(_) => "cargo",
+            Coordinates::Npm { .. } => "npm",
+            Coordinates::Go { .. } | Coordinates::AnyGo(_) => "go",
+        }
+    }
+
+    /// The name of the package on deps.dev.
+    pub fn package_slug(&self) -> Cow<'_, str> {
+        match self {
+            Coordinates::Maven {
+                group_id,
+                artifact_id,
+            } => Cow::Owned(format!("{}:{}", group_id, artifact_id)),
+            Coordinates::Cargo(package) => Cow::Borrowed(package),
+            Coordinates::Npm {
+                scope: Some(scope),
+                package,
+            } => Cow::Owned(format!("@{}/{}", scope, package)),
+            Coordinates::Npm {
+                scope: None,
+                package,
+            } => Cow::Borrowed(package),
+            Coordinates::Go { user, module } => {
+                Cow::Owned(format!("github.com/{}/{}", user, module))
+            }
+            Coordinates::AnyGo(go) => Cow::Borrowed(go),
+        }
+    }
+}
+
+/// Why a check could not be parsed.
+///
+/// The [`Display`] of the errors is plain text, any highlighting is left to the caller.
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum Error {
+    /// A part of the coordinates, e.g. `artifact_id`, is missing in the input.
+    Missing(&'static str, String),
+    /// A requirement is not a valid range.
+    InvalidRange(String, semver::Error),
+    /// An `include=` or `exclude=` segment is not a valid regular expression.
+    InvalidPattern(String, regex::Error),
+    /// A `scheme=` segment names an unknown version scheme.
+    InvalidScheme(String),
+    /// A `within=` segment is not a valid duration.
+    InvalidDuration(String),
+    /// An error in a list of checks, with the 1-based line number.
+    Line(usize, Box<Error>),
+}
+
+impl FromStr for VersionCheck {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_coordinates(s)
+    }
+}
+
+/// Parses a check in the form of `[system:]package[:requirement]*`.
+///
+/// Instead of a requirement, a segment can also be `include=REGEX` or `exclude=REGEX`
+/// to only consider versions that match or do not match the pattern,
+/// `scheme=calver` to read versions and requirements as calendar versions,
+/// or `within=DURATION` like `1y`, `6mo`, `2w`, or `30d` to only consider versions released within that time.
+/// A `:` inside a segment, like in the regex `(?:rc|beta)`, is escaped as `\:`, e.g. `exclude=(?\:rc|beta)`.
+pub fn parse_coordinates(input: &str) -> Result<VersionCheck, Error> {
+    let segments = split_segments(input);
+    let mut segments = segments.iter().map(|segment| segment.trim()).peekable();
+
+    let coordinates = match segments.next() {
+        Some("maven") => {
+            let group_id = match segments.next() {
+                Some(group_id) if !group_id.is_empty() => group_id,
+                _ => return Err(Error::Missing("group_id", input.into())),
+            };
+
+            let artifact_id = match segments.next() {
+                Some(artifact_id) if !artifact_id.is_empty() => artifact_id,
+                _ => return Err(Error::Missing("artifact_id", input.into())),
+            };
+
+            Coordinates::Maven {
+                group_id: group_id.into(),
+                artifact_id: artifact_id.into(),
+            }
+        }
+        Some("cargo") => {
+            let package = match segments.next() {
+                Some(package) if !package.is_empty() => package,
+                Some(_) => match segments.next() {
+                    Some(package) if !package.is_empty() => package,
+                    _ => return Err(Error::Missing("package", input.into())),
+                },
+                _ => return Err(Error::Missing("package", input.into())),
+            };
+
+            Coordinates::Cargo(package.into())
+        }
+        Some("npm") => match segments.next() {
+            Some(scope_or_package) if !scope_or_package.is_empty() => {
+                if scope_or_package.starts_with('@') {
+                    let scope = scope_or_package.trim_start_matches('@');
+                    if scope.is_empty() {
+                        return Err(Error::Missing("scope", input.into()));
+                    }
+                    if let Some((scope, package)) = scope.split_once('/') {
+                        if package.is_empty() {
+                            return Err(Error::Missing("package", input.into()));
+                        }
+                        Coordinates::Npm {
+                            scope: Some(scope.into()),
+                            package: package.into(),
+                        }
+                    } else {
+                        let package = match segments.next() {
+                            Some(package) if !package.is_empty() => package,
+                            _ => return Err(Error::Missing("package", input.into())),
+                        };
+                        Coordinates::Npm {
+                            scope: Some(scope.into()),
+                            package: package.into(),
+                        }
+                    }
+                } else if let Some((scope, package)) = scope_or_package.split_once('/') {
+                    if scope.is_empty() {
+                        return Err(Error::Missing("scope", input.into()));
+                    }
+                    if package.is_empty() {
+                        return Err(Error::Missing("package", input.into()));
+                    }
+                    Coordinates::Npm {
+                        scope: Some(scope.into()),
+                        package: package.into(),
+                    }
+                } else {
+                    match segments.peek() {
+                        Some(package)
+                            if !package.is_empty()
+                                && option(package).is_none()
+                                && Requirement::parse(package).is_err() =>
+                        {
+                            let coords = Coordinates::Npm {
+                                scope: Some(scope_or_package.into()),
+                                package: (*package).into(),
+                            };
+                            let _ = segments.next();
+                            coords
+                        }
+                        _ => Coordinates::Npm {
+                            scope: None,
+                            package: scope_or_package.into(),
+                        },
+                    }
+                }
+            }
+            _ => return Err(Error::Missing("package", input.into())),
+        },
+        Some("go") => match segments.next() {
+            Some(gomod) if !gomod.is_empty() && gomod.contains('/') => {
+                Coordinates::AnyGo(gomod.into())
+            }
+            Some(user) if !user.is_empty() => match segments.next() {
+                Some(gomod) if !gomod.is_empty() => Coordinates::Go {
+                    user: user.into(),
+                    module: gomod.into(),
+                },
+                _ => return Err(Error::Missing("module", input.into())),
+            },
+            _ => return Err(Error::Missing("user", input.into())),
+        },
+        Some("github.com") => {
+            let user = match segments.next() {
+                Some(user) if !user.is_empty() => user,
+                _ => return Err(Error::Missing("user", input.into())),
+            };
+
+            let module = match segments.next() {
+                Some(module) if !module.is_empty() => module,
+                _ => return Err(Error::Missing("module", input.into())),
+            };
+
+            Coordinates::Go {
+                user: user.into(),
+                module: module.into(),
+            }
+        }
+        Some(group_id) if !group_id.is_empty() => {
+            let artifact_id = match segments.next() {
+                Some(artifact_id) if !artifact_id.is_empty() => artifact_id,
+                _ => return Err(Error::Missing("artifact_id", input.into())),
+            };
+
+            Coordinates::Maven {
+                group_id: group_id.into(),
+                artifact_id: artifact_id.into(),
+            }
+        }
+        _ => return Err(Error::Missing("group_id", input.into())),
+    };
+
+    let mut requirements = Vec::new();
+    let mut filter = VersionFilter::new();
+    let mut scheme = VersionScheme::default();
+    let mut released_within = None;
+    for segment in segments {
+        match option(segment) {
+            Some((option @ ("include" | "exclude"), pattern)) => {
+                let pattern = regex::Regex::new(pattern)
+                    .map_err(|e| Error::InvalidPattern(pattern.into(), e))?;
+                filter = if option == "include" {
+                    filter.include(pattern)
+                } else {
+                    filter.exclude(pattern)
+                };
+            }
+            Some(("scheme", name)) => {
+                scheme = name
+                    .parse()
+                    .map_err(|_| Error::InvalidScheme(name.into()))?;
+            }
+            Some((_, duration)) => {
+                released_within = Some(
+                    parse_duration(duration)
+                        .ok_or_else(|| Error::InvalidDuration(duration.into()))?,
+                );
+            }
+            None => requirements.push(segment),
+        }
+    }
+
+    // the scheme can come after the requirements, but changes how they are read
+    let versions = requirements
+        .into_iter()
+        .map(|version| match scheme {
+            VersionScheme::CalVer if !version.is_empty() => Requirement::parse_calver(version)
+                .map_err(|e| Error::InvalidRange(version.into(), e)),
+            _ => parse_version(&coordinates, version),
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(VersionCheck {
+        coordinates,
+        versions,
+        current: None,
+        filter,
+        scheme,
+        released_within,
+        name: None,
+    })
+}
+
+/// Splits a check at every `:` that is not escaped as `\:`, and unescapes the segments.
+fn split_segments(input: &str) -> Vec<Cow<'_, str>> {
+    fn unescape(segment: &str) -> Cow<'_, str> {
+        if segment.contains("\\:") {
+            Cow::Owned(segment.replace("\\:", ":"))
+        } else {
+            Cow::Borrowed(segment)
+        }
+    }
+
+    let mut segments = Vec::new();
+    let mut start = 0;
+    for (idx, _) in input.match_indices(':') {
+        if !input[..idx].ends_with('\\') {
+            segments.push(unescape(&input[start..idx]));
+            start = idx + 1;
+        }
+    }
+    segments.push(unescape(&input[start..]));
+    segments
+}
+
+/// Splits a `key=value` segment with one of the known keys into the key and the value.
+fn option(segment: &str) -> Option<(&str, &str)> {
+    segment
+        .split_once('=')
+        .filter(|(key, _)| matches!(*key, "include" | "exclude" | "scheme" | "within"))
+}
+
+/// Parses a duration like `1y`, `6mo`, `2w`, or `30d`, where a month has 30 days and a year 365.
+///
+/// Months are spelled `mo`, so that `m` is not mistaken for the minutes of `--watch`.
+fn parse_duration(input: &str) -> Option<Duration> {
+    let (amount, days) = [("mo", 30), ("d", 1), ("w", 7), ("y", 365)]
+        .iter()
+        .find_map(|&(unit, days)| Some((input.strip_suffix(unit)?, days)))?;
+    let amount = amount.parse::<u64>().ok()?;
+    let seconds = amount.checked_mul(days)?.checked_mul(86400)?;
+    Some(Duration::from_secs(seconds))
+}
+
+/// Parses one check per line, skipping blank lines and `#` comments.
+///
+/// Errors are reported together with their 1-based line number.
+pub fn parse_coordinates_list(input: &str) -> Result<Vec<VersionCheck>, Error> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line.split('#').next().unwrap_or_default()))
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(line_no, line)| {
+            parse_coordinates(line).map_err(|e| Error::Line(line_no, Box::new(e)))
+        })
+        .collect()
+}
+
+pub(crate) fn parse_version(
+    coordinates: &Coordinates,
+    version: &str,
+) -> Result<Requirement, Error> {
+    if version.is_empty() {
+        Ok(Requirement::STAR)
+    } else {
+        coordinates
+            .parse_requirement(version)
+            .map_err(|e| Error::InvalidRange(version.into(), e))
+    }
+}
+
+/// Where the syntax of requirements is documented.
+pub const SEMVER_RANGE_SYNTAX: &str = "https://www.npmjs.com/package/semver#advanced-range-syntax";
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Missing(what, input) => write!(f, "Missing {} in {}", what, input),
+            Error::InvalidRange(input, _) => write!(
+                f,
+                "Could not parse {} into a semantic version range. Please provide a valid range according to {}",
+                input,
+                SEMVER_RANGE_SYNTAX,
+            ),
+            Error::InvalidPattern(input, e) => write!(
+                f,
+                "Could not parse {} into a regular expression: {}",
+                input, e
+            ),
+            Error::InvalidScheme(input) => write!(
+                f,
+                "Unknown version scheme {}, expected one of semver or calver",
+                input
+            ),
+            Error::InvalidDuration(input) => write!(
+                f,
+                "Could not parse {} into a duration like 1y, 6mo, 2w, or 30d",
+                input
+            ),
+            Error::Line(line, error) => write!(f, "{} on line {}", error, line),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::InvalidRange(_, src) => Some(src),
+            Error::InvalidPattern(_, src) => Some(src),
+            Error::Line(_, src) => src.source(),
+            Error::Missing(..) | Error::InvalidScheme(_) | Error::InvalidDuration(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(":foo", Error::Missing("group_id", ":foo".into()); "empty_group_id_1")]
+    #[test_case(":foo:", Error::Missing("group_id", ":foo:".into()); "empty_group_id_2")]
+    #[test_case("", Error::Missing("group_id", "".into()); "empty_group_id_3")]
+    #[test_case(":", Error::Missing("group_id", ":".into()); "empty_group_id_4")]
+    #[test_case("::", Error::Missing("group_id", "::".into()); "empty_group_id_5")]
+    #[test_case("  ", Error::Missing("group_id", "  ".into()); "empty_group_id_6")]
+    #[test_case("  :", Error::Missing("group_id", "  :".into()); "empty_group_id_7")]
+    #[test_case("foo:", Error::Missing("artifact_id", "foo:".into()); "empty_artifact_1")]
+    #[test_case("foo::", Error::Missing("artifact_id", "foo::".into()); "empty_artifact_2")]
+    #[test_case("foo: ", Error::Missing("artifact_id", "foo: ".into()); "empty_artifact_3")]
+    #[test_case("foo: :", Error::Missing("artifact_id", "foo: :".into()); "empty_artifact_4")]
+    #[test_case("foo", Error::Missing("artifact_id", "foo".into()); "missing_artifact")]
+    fn test_invalid_coords(arg: &str, expected: Error) {
+        match (parse_coordinates(arg).unwrap_err(), expected) {
+            (Error::Missing(lhs1, lhs2), Error::Missing(rhs1, rhs2)) => {
+                assert_eq!(lhs1, rhs1);
+                assert_eq!(lhs2, rhs2);
+            }
+            (lhs, rhs) => panic!("Different errors: left == {} right == {}", lhs, rhs),
+        }
+    }
+
+    #[test]
+    fn test_coordinates_list() {
+        let checks = parse_coordinates_list(
+            "# backend\ncargo:tokio:^1\n\n  cargo:serde # pinned by tokio\n\t\nfoo:bar:1\n",
+        )
+        .unwrap();
+        let packages = checks
+            .iter()
+            .map(|c| c.coordinates.package_slug())
+            .collect::<Vec<_>>();
+        assert_eq!(packages, ["tokio", "serde", "foo:bar"]);
+    }
+
+    #[test]
+    fn test_version_filter() {
+        let check =
+            parse_coordinates("maven:com.google.guava:guava:^31:exclude=-android$:include=^3")
+                .unwrap();
+        assert_eq!(check.versions.len(), 1);
+        assert!(check.filter.matches("31.1-jre"));
+        assert!(!check.filter.matches("31.1-android"));
+        assert!(!check.filter.matches("21.0"));
+
+        let check = parse_coordinates("npm:react:include=^17").unwrap();
+        assert_eq!(check.coordinates.package_slug(), "react");
+        assert!(check.versions.is_empty());
+    }
+
+    #[test]
+    fn test_escaped_colon() {
+        let check = parse_coordinates(r"npm:foo:^1:exclude=-(?\:rc|beta)\.\d+$").unwrap();
+        assert_eq!(check.versions, vec![Requirement::parse("^1").unwrap()]);
+        assert!(check.filter.matches("1.2.0"));
+        assert!(!check.filter.matches("1.2.0-rc.1"));
+        assert!(!check.filter.matches("1.2.0-beta.2"));
+
+        let check = parse_coordinates(r"npm:foo:include=a\:b").unwrap();
+        assert!(check.versions.is_empty());
+        assert!(check.filter.matches("1.0.0-a:b"));
+        assert!(!check.filter.matches("1.0.0-a"));
+    }
+
+    #[test]
+    fn test_calver() {
+        let check = parse_coordinates("npm:foo:2023:>=20230601:scheme=calver:within=1y").unwrap();
+        assert_eq!(check.coordinates.package_slug(), "foo");
+        assert_eq!(check.scheme, VersionScheme::CalVer);
+        assert_eq!(
+            check.versions,
+            vec![
+                Requirement::parse("=2023").unwrap(),
+                Requirement::parse(">=2023.6.1").unwrap()
+            ]
+        );
+        assert_eq!(
+            check.released_within,
+            Some(Duration::from_secs(365 * 86400))
+        );
+
+        let check = parse_coordinates("cargo:foo:within=30d").unwrap();
+        assert_eq!(check.scheme, VersionScheme::Semver);
+        assert!(check.versions.is_empty());
+        assert_eq!(check.released_within, Some(Duration::from_secs(30 * 86400)));
+
+        let check = parse_coordinates("cargo:foo:within=6mo").unwrap();
+        assert_eq!(
+            check.released_within,
+            Some(Duration::from_secs(6 * 30 * 86400))
+        );
+    }
+
+    #[test_case("foo:bar:scheme=date"; "unknown scheme")]
+    #[test_case("foo:bar:within=1"; "duration without unit")]
+    #[test_case("foo:bar:within=y"; "duration without amount")]
+    #[test_case("foo:bar:within="; "empty duration")]
+    #[test_case("foo:bar:within=6m"; "m is not a month")]
+    #[test_case("foo:bar:within=1é"; "non ascii unit")]
+    #[test_case("foo:bar:within=é"; "only non ascii")]
+    #[test_case("foo:bar:within=99999999999999y"; "overflow")]
+    #[test_case("foo:bar:within=99999999999999999999d"; "amount too large")]
+    fn test_invalid_options(input: &str) {
+        assert!(matches!(
+            parse_coordinates(input),
+            Err(Error::InvalidScheme(_) | Error::InvalidDuration(_))
+        ));
+    }
+
+    #[test_case("foo:bar\nfoo:\n", 2, "Missing artifact_id in foo: on line 2"; "missing")]
+    #[test_case("# comment\n\nfoo:bar:qux", 3, "Could not parse qux into a semantic version range. Please provide a valid range according to https://www.npmjs.com/package/semver#advanced-range-syntax on line 3"; "invalid range")]
+    fn test_coordinates_list_errors(input: &str, expected_line: usize, expected_msg: &str) {
+        let err = parse_coordinates_list(input).unwrap_err();
+        assert_eq!(err.to_string(), expected_msg);
+        match err {
+            Error::Line(line, _) => assert_eq!(line, expected_line),
+            other => panic!("Expected a line error, got {:?}", other),
+        }
+    }
+}
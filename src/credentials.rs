@@ -0,0 +1,232 @@
+use reqwest::{RequestBuilder, Url};
+use serde::Deserialize;
+use std::{collections::HashMap, env, fmt::Display, fs, io, path::PathBuf};
+
+/// Credentials for registry hosts, attached to every request against that host.
+///
+/// Requests only go to deps.dev or to the registry urls configured with [`crate::Client::with_registry`],
+/// so those are the hosts that credentials are looked up for.
+/// Sources are considered in the following order, the first one that has an entry
+/// for a host wins:
+///
+///  1. Environment variables `LATEST_VERSION_TOKEN_<HOST>` or
+///     `LATEST_VERSION_USERNAME_<HOST>` and `LATEST_VERSION_PASSWORD_<HOST>`,
+///     where `<HOST>` is the uppercased host with every non-alphanumeric character replaced by `_`.
+///  2. The credentials file at `$LATEST_VERSION_CREDENTIALS`
+///     or `latest-version/credentials.toml` in the user config dir.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Credentials {
+    hosts: HashMap<String, Credential>,
+}
+
+/// How requests against a host are authenticated.
+///
+/// The `Debug` output leaves out tokens and passwords.
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
+pub enum Credential {
+    /// `Authorization: Bearer <token>`
+    Bearer(String),
+    /// `Authorization: Basic <base64(username:password)>`
+    Basic {
+        username: String,
+        password: Option<String>,
+    },
+}
+
+/// Why the credentials could not be loaded.
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum Error {
+    /// The credentials file exists, but could not be read.
+    Read(PathBuf, io::Error),
+    /// The credentials file is not valid.
+    Parse(PathBuf, toml::de::Error),
+}
+
+impl Credentials {
+    /// Loads the credentials from all sources.
+    pub fn load() -> Result<Self, Error> {
+        let file = match credentials_file() {
+            Some(file) if file.exists() => file,
+            _ => return Ok(Self::default()),
+        };
+        let content = match fs::read_to_string(&file) {
+            Ok(content) => content,
+            Err(e) => return Err(Error::Read(file, e)),
+        };
+        Self::from_credentials_toml(&content).map_err(|e| Error::Parse(file, e))
+    }
+
+    /// Configures the credential for a host, replacing any previous one.
+    pub fn insert(&mut self, host: impl Into<String>, credential: Credential) {
+        let _ = self.hosts.insert(host.into(), credential);
+    }
+
+    pub(crate) fn authorize(&self, request: RequestBuilder, url: &Url) -> RequestBuilder {
+        let host = match url.host_str() {
+            Some(host) => host,
+            None => return request,
+        };
+
+        match from_env(host).as_ref().or_else(|| self.hosts.get(host)) {
+            Some(Credential::Bearer(token)) => request.bearer_auth(token),
+            Some(Credential::Basic { username, password }) => {
+                request.basic_auth(username, password.as_ref())
+            }
+            None => request,
+        }
+    }
+
+    fn from_credentials_toml(content: &str) -> Result<Self, toml::de::Error> {
+        #[derive(Deserialize)]
+        struct File {
+            #[serde(default)]
+            hosts: HashMap<String, Entry>,
+        }
+
+        #[derive(Deserialize)]
+        struct Entry {
+            token: Option<String>,
+            username: Option<String>,
+            password: Option<String>,
+        }
+
+        let file = toml::from_str::<File>(content)?;
+        let hosts = file
+            .hosts
+            .into_iter()
+            .filter_map(|(host, entry)| {
+                let credential = match entry {
+                    Entry {
+                        token: Some(token), ..
+                    } => Credential::Bearer(token),
+                    Entry {
+                        username: Some(username),
+                        password,
+                        ..
+                    } => Credential::Basic { username, password },
+                    _ => return None,
+                };
+                Some((host, credential))
+            })
+            .collect();
+
+        Ok(Self { hosts })
+    }
+}
+
+impl std::fmt::Debug for Credential {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const REDACTED: &str = "<redacted>";
+        match self {
+            Credential::Bearer(_) => f.debug_tuple("Bearer").field(&REDACTED).finish(),
+            Credential::Basic { username, password } => f
+                .debug_struct("Basic")
+                .field("username", username)
+                .field("password", &password.as_ref().map(|_| REDACTED))
+                .finish(),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Read(file, _) => write!(f, "Could not read {}", file.display()),
+            Error::Parse(file, _) => write!(f, "Could not parse {}", file.display()),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Read(_, src) => Some(src),
+            Error::Parse(_, src) => Some(src),
+        }
+    }
+}
+
+fn from_env(host: &str) -> Option<Credential> {
+    let suffix = host
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+
+    let var = |name: &str| env::var(format!("LATEST_VERSION_{}_{}", name, suffix)).ok();
+
+    if let Some(token) = var("TOKEN") {
+        return Some(Credential::Bearer(token));
+    }
+    var("USERNAME").map(|username| Credential::Basic {
+        username,
+        password: var("PASSWORD"),
+    })
+}
+
+fn credentials_file() -> Option<PathBuf> {
+    env::var_os("LATEST_VERSION_CREDENTIALS")
+        .map(PathBuf::from)
+        .or_else(|| {
+            dirs::config_dir().map(|d| d.join(env!("CARGO_PKG_NAME")).join("credentials.toml"))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn credentials_toml() {
+        let credentials = Credentials::from_credentials_toml(
+            r#"
+            [hosts."maven.example.com"]
+            username = "user"
+            password = "secret"
+
+            [hosts."npm.pkg.github.com"]
+            token = "ghp_token"
+
+            [hosts."empty.example.com"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            credentials.hosts.get("maven.example.com"),
+            Some(&Credential::Basic {
+                username: "user".into(),
+                password: Some("secret".into())
+            })
+        );
+        assert_eq!(
+            credentials.hosts.get("npm.pkg.github.com"),
+            Some(&Credential::Bearer("ghp_token".into()))
+        );
+        assert_eq!(credentials.hosts.get("empty.example.com"), None);
+    }
+
+    #[test]
+    fn debug_redacts_secrets() {
+        let mut credentials = Credentials::default();
+        credentials.insert("npm.pkg.github.com", Credential::Bearer("ghp_token".into()));
+        credentials.insert(
+            "maven.example.com",
+            Credential::Basic {
+                username: "user".into(),
+                password: Some("secret".into()),
+            },
+        );
+        let debug = format!("{:?}", credentials);
+        assert!(!debug.contains("ghp_token"));
+        assert!(!debug.contains("secret"));
+        assert!(debug.contains("user"));
+    }
+}
@@ -0,0 +1,117 @@
+use crate::Coordinates;
+use std::fmt::Display;
+
+/// A dependency declaration that can be pasted into a build file or run in a shell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Declaration {
+    pub format: DeclarationFormat,
+    pub text: String,
+}
+
+/// Where a [`Declaration`] goes.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeclarationFormat {
+    /// A line in the `[dependencies]` of a `Cargo.toml`
+    CargoToml,
+    /// An entry in the `dependencies` of a `package.json`
+    PackageJson,
+    /// A `<dependency>` in a `pom.xml`
+    Maven,
+    /// A dependency in a `build.gradle(.kts)`
+    Gradle,
+    /// A `go get` command
+    GoGet,
+}
+
+impl Coordinates {
+    /// Declarations of a dependency on `version` of this package, in the native syntax of its ecosystem.
+    ///
+    /// The version is used as the lowest allowed version with the default requirement of the ecosystem,
+    /// i.e. a caret requirement for cargo and npm, and the exact version for maven and go.
+    pub fn declarations(&self, version: &str) -> Vec<Declaration> {
+        let declaration = |format, text| Declaration { format, text };
+        match self {
+            Coordinates::Maven {
+                group_id,
+                artifact_id,
+            } => vec![
+                declaration(
+                    DeclarationFormat::Maven,
+                    format!(
+                        "<dependency>\n  <groupId>{}</groupId>\n  <artifactId>{}</artifactId>\n  <version>{}</version>\n</dependency>",
+                        group_id, artifact_id, version
+                    ),
+                ),
+                declaration(
+                    DeclarationFormat::Gradle,
+                    format!("implementation(\"{}:{}:{}\")", group_id, artifact_id, version),
+                ),
+            ],
+            Coordinates::Cargo(package) => vec![declaration(
+                DeclarationFormat::CargoToml,
+                format!("{} = \"{}\"", package, version),
+            )],
+            Coordinates::Npm { .. } => vec![declaration(
+                DeclarationFormat::PackageJson,
+                format!("\"{}\": \"^{}\"", self.package_slug(), version),
+            )],
+            Coordinates::Go { .. } | Coordinates::AnyGo(_) => {
+                let version = version.strip_prefix('v').unwrap_or(version);
+                vec![declaration(
+                    DeclarationFormat::GoGet,
+                    format!("go get {}@v{}", self.package_slug(), version),
+                )]
+            }
+        }
+    }
+}
+
+impl Display for DeclarationFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            DeclarationFormat::CargoToml => "Cargo.toml",
+            DeclarationFormat::PackageJson => "package.json",
+            DeclarationFormat::Maven => "pom.xml",
+            DeclarationFormat::Gradle => "build.gradle",
+            DeclarationFormat::GoGet => "go get",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn texts(coordinates: Coordinates, version: &str) -> Vec<String> {
+        coordinates
+            .declarations(version)
+            .into_iter()
+            .map(|d| d.text)
+            .collect()
+    }
+
+    #[test]
+    fn maven() {
+        let coordinates = Coordinates::Maven {
+            group_id: "org.neo4j".into(),
+            artifact_id: "neo4j".into(),
+        };
+        assert_eq!(
+            texts(coordinates, "5.2.0"),
+            vec![
+                "<dependency>\n  <groupId>org.neo4j</groupId>\n  <artifactId>neo4j</artifactId>\n  <version>5.2.0</version>\n</dependency>",
+                "implementation(\"org.neo4j:neo4j:5.2.0\")",
+            ]
+        );
+    }
+
+    #[test_case(Coordinates::Cargo("serde".into()), "1.0.150", "serde = \"1.0.150\""; "cargo")]
+    #[test_case(Coordinates::Npm { scope: Some("types".into()), package: "node".into() }, "18.11.9", "\"@types/node\": \"^18.11.9\""; "npm")]
+    #[test_case(Coordinates::Go { user: "neo4j".into(), module: "neo4j-go-driver".into() }, "v4.4.4", "go get github.com/neo4j/neo4j-go-driver@v4.4.4"; "go")]
+    #[test_case(Coordinates::AnyGo("golang.org/x/text".into()), "0.4.0", "go get golang.org/x/text@v0.4.0"; "go without v")]
+    fn single_declaration(coordinates: Coordinates, version: &str, expected: &str) {
+        assert_eq!(texts(coordinates, version), vec![expected]);
+    }
+}
@@ -0,0 +1,193 @@
+use color_eyre::eyre::Result;
+use console::style;
+use latest_version::{Client, Coordinates, DependencyGraph, Requirement};
+use serde_json::{json, Value};
+use std::{collections::HashSet, fmt::Write};
+use tokio::io::{self, AsyncWriteExt};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Deps {
+    /// Also show the dependencies of dependencies.
+    pub(crate) transitive: bool,
+    pub(crate) format: DepsFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DepsFormat {
+    Tree,
+    Json,
+}
+
+/// Prints the dependencies of every latest version.
+pub(crate) async fn report(
+    client: &Client,
+    coordinates: &Coordinates,
+    latest: &[(Requirement, Option<String>)],
+    deps: Deps,
+) -> Result<()> {
+    let mut msg = String::with_capacity(256);
+    for version in latest.iter().filter_map(|(_, v)| v.as_deref()) {
+        let graph = client.query_dependencies(coordinates, version).await?;
+        match deps.format {
+            DepsFormat::Tree => {
+                let _ = writeln!(
+                    msg,
+                    "Dependencies of {}:{} {}",
+                    style(coordinates.system_slug()).magenta(),
+                    style(coordinates.package_slug()).blue(),
+                    style(version).green().bold()
+                );
+                write_tree(
+                    &mut msg,
+                    &graph,
+                    0,
+                    "",
+                    deps.transitive,
+                    &mut HashSet::new(),
+                );
+            }
+            DepsFormat::Json => {
+                let json = json!({
+                    "system": coordinates.system_slug(),
+                    "package": coordinates.package_slug(),
+                    "version": version,
+                    "dependencies": json_dependencies(&graph, 0, deps.transitive, &mut HashSet::new()),
+                });
+                let _ = writeln!(msg, "{}", json);
+            }
+        }
+    }
+
+    io::stdout().write_all(msg.as_bytes()).await?;
+    Ok(())
+}
+
+/// Writes the dependencies of `node` like `cargo tree`.
+/// Packages that were already expanded are marked with `(*)` and not expanded again.
+fn write_tree(
+    msg: &mut String,
+    graph: &DependencyGraph,
+    node: usize,
+    prefix: &str,
+    transitive: bool,
+    expanded: &mut HashSet<usize>,
+) {
+    let _ = expanded.insert(node);
+    let edges = graph.dependencies(node).collect::<Vec<_>>();
+    for (idx, edge) in edges.iter().enumerate() {
+        let last = idx + 1 == edges.len();
+        let dependency = &graph.nodes[edge.to];
+        let _ = write!(
+            msg,
+            "{}{} {} {}",
+            prefix,
+            if last { "└──" } else { "├──" },
+            style(&dependency.name).blue(),
+            style(&dependency.version).green()
+        );
+        if !edge.requirement.is_empty() {
+            let _ = write!(msg, " ({})", style(&edge.requirement).cyan());
+        }
+        if !transitive {
+            msg.push('\n');
+            continue;
+        }
+        if expanded.contains(&edge.to) {
+            if graph.dependencies(edge.to).next().is_some() {
+                msg.push_str(" (*)");
+            }
+            msg.push('\n');
+            continue;
+        }
+        msg.push('\n');
+        let prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+        write_tree(msg, graph, edge.to, &prefix, transitive, expanded);
+    }
+}
+
+fn json_dependencies(
+    graph: &DependencyGraph,
+    node: usize,
+    transitive: bool,
+    expanded: &mut HashSet<usize>,
+) -> Value {
+    let _ = expanded.insert(node);
+    graph
+        .dependencies(node)
+        .map(|edge| {
+            let dependency = &graph.nodes[edge.to];
+            let mut json = json!({
+                "package": dependency.name,
+                "version": dependency.version,
+                "requirement": edge.requirement,
+            });
+            if transitive && !expanded.contains(&edge.to) {
+                json["dependencies"] = json_dependencies(graph, edge.to, transitive, expanded);
+            }
+            json
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use latest_version::{Edge, Node};
+
+    fn graph() -> DependencyGraph {
+        let node = |name: &str| Node {
+            name: name.into(),
+            version: "1.0.0".into(),
+        };
+        let edge = |from, to| Edge {
+            from,
+            to,
+            requirement: "^1".into(),
+        };
+        DependencyGraph {
+            nodes: vec![node("root"), node("a"), node("b"), node("c")],
+            edges: vec![edge(0, 1), edge(0, 2), edge(1, 3), edge(2, 1)],
+        }
+    }
+
+    #[test]
+    fn direct_tree() {
+        console::set_colors_enabled(false);
+        let mut msg = String::new();
+        write_tree(&mut msg, &graph(), 0, "", false, &mut HashSet::new());
+        assert_eq!(msg, "├── a 1.0.0 (^1)\n└── b 1.0.0 (^1)\n");
+    }
+
+    #[test]
+    fn transitive_tree() {
+        console::set_colors_enabled(false);
+        let mut msg = String::new();
+        write_tree(&mut msg, &graph(), 0, "", true, &mut HashSet::new());
+        assert_eq!(
+            msg,
+            "├── a 1.0.0 (^1)\n│   └── c 1.0.0 (^1)\n└── b 1.0.0 (^1)\n    └── a 1.0.0 (^1) (*)\n"
+        );
+    }
+
+    #[test]
+    fn transitive_json() {
+        let json = json_dependencies(&graph(), 0, true, &mut HashSet::new());
+        assert_eq!(
+            json,
+            json!([
+                {
+                    "package": "a", "version": "1.0.0", "requirement": "^1",
+                    "dependencies": [
+                        { "package": "c", "version": "1.0.0", "requirement": "^1", "dependencies": [] }
+                    ]
+                },
+                {
+                    "package": "b", "version": "1.0.0", "requirement": "^1",
+                    "dependencies": [
+                        { "package": "a", "version": "1.0.0", "requirement": "^1" }
+                    ]
+                }
+            ])
+        );
+    }
+}
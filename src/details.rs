@@ -0,0 +1,145 @@
+use serde_json::Value;
+
+/// Details about a single published version of a package.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct VersionDetails {
+    pub version: String,
+    /// Known security advisories that affect this version.
+    pub advisories: Vec<Advisory>,
+    /// The declared licenses, usually as SPDX expressions.
+    pub licenses: Vec<String>,
+}
+
+/// A security advisory, e.g. from GitHub or OSV.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Advisory {
+    pub id: String,
+    /// The severity as reported by the source, e.g. `HIGH` or a CVSS score.
+    pub severity: Option<String>,
+    pub title: Option<String>,
+}
+
+impl VersionDetails {
+    /// Reads the details from a response of the version endpoint.
+    ///
+    /// The details can be at the root or nested in a `version` object, unknown fields are ignored.
+    pub(crate) fn from_json(version: &str, response: &Value) -> Self {
+        let details = response
+            .get("version")
+            .filter(|v| v.is_object())
+            .unwrap_or(response);
+
+        let advisories = details
+            .get("advisories")
+            .or_else(|| details.get("advisoryKeys"))
+            .and_then(Value::as_array)
+            .map_or_else(Vec::new, |advisories| {
+                advisories.iter().filter_map(Advisory::from_json).collect()
+            });
+
+        let licenses = details
+            .get("licenses")
+            .and_then(Value::as_array)
+            .map_or_else(Vec::new, |licenses| {
+                licenses
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .filter(|l| !l.is_empty())
+                    .map(String::from)
+                    .collect()
+            });
+
+        Self {
+            version: version.into(),
+            advisories,
+            licenses,
+        }
+    }
+}
+
+impl Advisory {
+    fn from_json(advisory: &Value) -> Option<Self> {
+        let id = ["sourceID", "id"]
+            .iter()
+            .find_map(|key| advisory.get(key))
+            .or_else(|| advisory.get("advisoryKey")?.get("id"))
+            .and_then(Value::as_str)?;
+
+        let severity = ["severity", "gitHubSeverity", "cvss3Score"]
+            .iter()
+            .filter_map(|key| advisory.get(key))
+            .find_map(|severity| match severity {
+                Value::String(s) if !s.is_empty() => Some(s.clone()),
+                Value::Number(n) => Some(n.to_string()),
+                _ => None,
+            });
+
+        let title = advisory
+            .get("title")
+            .and_then(Value::as_str)
+            .filter(|t| !t.is_empty())
+            .map(String::from);
+
+        Some(Self {
+            id: id.into(),
+            severity,
+            title,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn nested_advisories() {
+        let response = json!({
+            "package": { "system": "NPM", "name": "minimist" },
+            "version": {
+                "version": "1.2.5",
+                "licenses": ["MIT"],
+                "advisories": [
+                    {
+                        "source": "GHSA",
+                        "sourceID": "GHSA-xvch-5gv4-984h",
+                        "severity": "CRITICAL",
+                        "title": "Prototype Pollution in minimist"
+                    },
+                    { "advisoryKey": { "id": "GHSA-vh95-rmgr-6w4m" }, "cvss3Score": 5.6 }
+                ]
+            }
+        });
+        let details = VersionDetails::from_json("1.2.5", &response);
+        assert_eq!(details.licenses, vec![String::from("MIT")]);
+        assert_eq!(
+            details.advisories,
+            vec![
+                Advisory {
+                    id: "GHSA-xvch-5gv4-984h".into(),
+                    severity: Some("CRITICAL".into()),
+                    title: Some("Prototype Pollution in minimist".into()),
+                },
+                Advisory {
+                    id: "GHSA-vh95-rmgr-6w4m".into(),
+                    severity: Some("5.6".into()),
+                    title: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn top_level_details() {
+        let response = json!({
+            "versionKey": { "version": "1.0.0" },
+            "advisoryKeys": [],
+            "licenses": ["MIT OR Apache-2.0", ""]
+        });
+        let details = VersionDetails::from_json("1.0.0", &response);
+        assert_eq!(details.version, "1.0.0");
+        assert!(details.advisories.is_empty());
+        assert_eq!(details.licenses, vec![String::from("MIT OR Apache-2.0")]);
+    }
+}
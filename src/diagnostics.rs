@@ -0,0 +1,60 @@
+use color_eyre::eyre::Result;
+use console::style;
+use latest_version::{Coordinates, Versions};
+use tokio::io::{self, AsyncWriteExt};
+
+/// Prints the versions of a package that could not be parsed to stderr, if there are any.
+pub(crate) async fn report(
+    coordinates: &Coordinates,
+    versions: &Versions,
+    natural_fallback: bool,
+) -> Result<()> {
+    let unparsed = versions.unparsed();
+    if unparsed.is_empty() {
+        return Ok(());
+    }
+    let msg = message(coordinates, &unparsed, natural_fallback);
+    io::stderr().write_all(msg.as_bytes()).await?;
+    Ok(())
+}
+
+fn message(coordinates: &Coordinates, unparsed: &[&str], natural_fallback: bool) -> String {
+    let (versions, verb) = match unparsed.len() {
+        1 => ("version", "is"),
+        _ => ("versions", "are"),
+    };
+    let handling = if natural_fallback {
+        "ordered naturally"
+    } else {
+        "skipped"
+    };
+    format!(
+        "{} {} of {}:{} could not be parsed and {} {}: {}\n",
+        unparsed.len(),
+        versions,
+        style(coordinates.system_slug()).magenta(),
+        style(coordinates.package_slug()).blue(),
+        verb,
+        handling,
+        style(unparsed.join(", ")).yellow()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unparsed_message() {
+        console::set_colors_enabled(false);
+        let coordinates = Coordinates::Cargo("foo".into());
+        assert_eq!(
+            message(&coordinates, &["r9", "latest"], false),
+            "2 versions of cargo:foo could not be parsed and are skipped: r9, latest\n"
+        );
+        assert_eq!(
+            message(&coordinates, &["r9"], true),
+            "1 version of cargo:foo could not be parsed and is ordered naturally: r9\n"
+        );
+    }
+}
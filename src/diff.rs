@@ -0,0 +1,140 @@
+use crate::Config;
+use color_eyre::eyre::Result;
+use console::style;
+use latest_version::{Client, Coordinates, Release, ReleaseStatus};
+use semver::Version;
+use std::fmt::Write;
+use tokio::io::{self, AsyncWriteExt};
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Diff {
+    pub(crate) coordinates: Coordinates,
+    pub(crate) from: Version,
+    pub(crate) to: Version,
+}
+
+/// Lists all versions that were published between two versions.
+pub(crate) async fn run(client: Client, config: Config, diff: Diff) -> Result<()> {
+    let versions = client.query_versions(&diff.coordinates).await?;
+    let mut versions = versions.pre_release_channel(config.pre_release_channel);
+    if let Some(filter) = &config.version_filter {
+        versions = versions.filter(filter);
+    }
+    let releases = versions.between(&diff.from, &diff.to, config.include_pre_releases);
+
+    let mut msg = String::with_capacity(256);
+    write_diff(&mut msg, &diff, &releases);
+    io::stdout().write_all(msg.as_bytes()).await?;
+    Ok(())
+}
+
+fn write_diff(msg: &mut String, diff: &Diff, releases: &[&Release]) {
+    let coordinates = &diff.coordinates;
+    if releases.is_empty() {
+        let _ = writeln!(
+            msg,
+            "No versions of {}:{} after {} up to {}",
+            style(coordinates.system_slug()).magenta(),
+            style(coordinates.package_slug()).blue(),
+            style(&diff.from).cyan(),
+            style(&diff.to).cyan()
+        );
+        return;
+    }
+
+    let _ = writeln!(
+        msg,
+        "{} versions of {}:{} after {} up to {}",
+        releases.len(),
+        style(coordinates.system_slug()).magenta(),
+        style(coordinates.package_slug()).blue(),
+        style(&diff.from).cyan(),
+        style(&diff.to).cyan()
+    );
+
+    let width = releases.iter().map(|r| r.version.len()).max().unwrap_or(0);
+    let mut previous = diff.from.clone();
+    for release in releases {
+        let _ = write!(
+            msg,
+            "  {}  {}",
+            style(format!("{:width$}", release.version, width = width)).green(),
+            release.published_on().unwrap_or("          ")
+        );
+        let marker = match release.status {
+            ReleaseStatus::Yanked => Some("yanked"),
+            ReleaseStatus::Deprecated => Some("deprecated"),
+            ReleaseStatus::Retracted => Some("retracted"),
+            _ => None,
+        };
+        if let Some(marker) = marker {
+            let _ = write!(msg, "  {}", style(marker).red());
+        }
+        if let Ok(version) = lenient_semver::parse(&release.version) {
+            if is_breaking(&previous, &version) {
+                let _ = write!(msg, "  {}", style("semver-major").yellow().bold());
+            }
+            previous = version;
+        }
+        msg.push('\n');
+    }
+}
+
+/// Whether `next` is not compatible to `previous`, where a new minor version of `0.x` counts as breaking.
+pub(crate) fn is_breaking(previous: &Version, next: &Version) -> bool {
+    previous.major != next.major || (previous.major == 0 && previous.minor != next.minor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("1.2.3", "1.3.0", false; "minor")]
+    #[test_case("1.2.3", "2.0.0", true; "major")]
+    #[test_case("0.2.3", "0.2.4", false; "zero patch")]
+    #[test_case("0.2.3", "0.3.0", true; "zero minor")]
+    fn test_is_breaking(previous: &str, next: &str, expected: bool) {
+        assert_eq!(
+            is_breaking(
+                &Version::parse(previous).unwrap(),
+                &Version::parse(next).unwrap()
+            ),
+            expected
+        );
+    }
+
+    #[test]
+    fn diff_output() {
+        console::set_colors_enabled(false);
+        let release = |version: &str, published_at: Option<&str>, status| Release {
+            version: version.into(),
+            published_at: published_at.map(String::from),
+            status,
+        };
+        let releases = [
+            release(
+                "1.9.0",
+                Some("2022-01-02T03:04:05Z"),
+                ReleaseStatus::Published,
+            ),
+            release("1.10.0", None, ReleaseStatus::Yanked),
+            release(
+                "2.0.0",
+                Some("2022-06-01T00:00:00Z"),
+                ReleaseStatus::Published,
+            ),
+        ];
+        let diff = Diff {
+            coordinates: Coordinates::Cargo("foo".into()),
+            from: Version::new(1, 8, 0),
+            to: Version::new(2, 0, 0),
+        };
+        let mut msg = String::new();
+        write_diff(&mut msg, &diff, &releases.iter().collect::<Vec<_>>());
+        assert_eq!(
+            msg,
+            "3 versions of cargo:foo after 1.8.0 up to 2.0.0\n  1.9.0   2022-01-02\n  1.10.0              yanked\n  2.0.0   2022-06-01  semver-major\n"
+        );
+    }
+}
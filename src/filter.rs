@@ -0,0 +1,73 @@
+use regex::Regex;
+
+/// Patterns that the version strings of a package have to match to be considered at all.
+///
+/// A version is kept if it matches any of the `include` patterns, or if there are none,
+/// and none of the `exclude` patterns.
+#[derive(Debug, Clone, Default)]
+pub struct VersionFilter {
+    include: Vec<Regex>,
+    exclude: Vec<Regex>,
+}
+
+impl VersionFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only keep versions that match `pattern` or one of the other include patterns.
+    pub fn include(mut self, pattern: Regex) -> Self {
+        self.include.push(pattern);
+        self
+    }
+
+    /// Drop versions that match `pattern`.
+    pub fn exclude(mut self, pattern: Regex) -> Self {
+        self.exclude.push(pattern);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    /// Whether `version` is kept by this filter.
+    pub fn matches(&self, version: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|p| p.is_match(version)))
+            && !self.exclude.iter().any(|p| p.is_match(version))
+    }
+}
+
+impl PartialEq for VersionFilter {
+    fn eq(&self, other: &Self) -> bool {
+        fn same(lhs: &[Regex], rhs: &[Regex]) -> bool {
+            lhs.len() == rhs.len() && lhs.iter().zip(rhs).all(|(l, r)| l.as_str() == r.as_str())
+        }
+        same(&self.include, &other.include) && same(&self.exclude, &other.exclude)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn regex(pattern: &str) -> Regex {
+        Regex::new(pattern).unwrap()
+    }
+
+    #[test_case(VersionFilter::new(), &["31.1-jre", "31.1-android", "5.4.0.Final", "1.0.0-nightly"]; "empty")]
+    #[test_case(VersionFilter::new().include(regex("-jre$")), &["31.1-jre"]; "include")]
+    #[test_case(VersionFilter::new().exclude(regex("-android$")).exclude(regex("nightly")), &["31.1-jre", "5.4.0.Final"]; "exclude")]
+    #[test_case(VersionFilter::new().include(regex("-jre$")).include(regex(r"\.Final$")), &["31.1-jre", "5.4.0.Final"]; "any include")]
+    #[test_case(VersionFilter::new().include(regex(r"^\d+\.\d+")).exclude(regex("-")), &["5.4.0.Final"]; "include and exclude")]
+    fn matching(filter: VersionFilter, expected: &[&str]) {
+        let versions = ["31.1-jre", "31.1-android", "5.4.0.Final", "1.0.0-nightly"];
+        let kept = versions
+            .iter()
+            .filter(|v| filter.matches(v))
+            .copied()
+            .collect::<Vec<_>>();
+        assert_eq!(kept, expected);
+    }
+}
@@ -0,0 +1,59 @@
+//! Temporary directories with files, for tests that read manifests, lockfiles, or workspaces.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// A directory in the system temp dir that is removed again when the fixture is dropped.
+#[derive(Debug)]
+pub(crate) struct Fixture {
+    dir: PathBuf,
+}
+
+impl Fixture {
+    /// Creates an empty directory that is named after the running test, so that no two tests share one.
+    pub(crate) fn new() -> Self {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        // the test harness names the thread of every test after the test, including the test case
+        let thread = std::thread::current();
+        let test = thread
+            .name()
+            .unwrap_or("test")
+            .replace(|c: char| !c.is_ascii_alphanumeric(), "-");
+        let dir = std::env::temp_dir().join(format!(
+            "latest-version-{}-{}-{}",
+            test,
+            std::process::id(),
+            COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        Self { dir }
+    }
+
+    /// Writes `content` to `file`, a path relative to the fixture, creating missing directories.
+    pub(crate) fn write(&self, file: &str, content: &str) -> &Self {
+        let path = self.dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+        self
+    }
+
+    /// The directory of the fixture.
+    pub(crate) fn path(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The path of `file` within the fixture.
+    pub(crate) fn join(&self, file: &str) -> PathBuf {
+        self.dir.join(file)
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
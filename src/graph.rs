@@ -0,0 +1,142 @@
+use serde_json::Value;
+
+/// The resolved dependency graph of a single version of a package.
+///
+/// The first node is the package itself.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DependencyGraph {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+}
+
+/// A package in the dependency graph, resolved to a version.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Node {
+    pub name: String,
+    pub version: String,
+}
+
+/// A dependency from one node on another, as indices into the nodes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edge {
+    pub from: usize,
+    pub to: usize,
+    /// The requirement as it is declared by the depending package.
+    pub requirement: String,
+}
+
+impl DependencyGraph {
+    /// Reads the graph from a response of the dependencies endpoint.
+    ///
+    /// Nodes can have their name and version either in a `versionKey` or in `package` and `version`.
+    pub(crate) fn from_json(response: &Value) -> Self {
+        let nodes =
+            response
+                .get("nodes")
+                .and_then(Value::as_array)
+                .map_or_else(Vec::new, |nodes| {
+                    // keep unknown nodes, the edges refer to the nodes by their index
+                    nodes
+                        .iter()
+                        .map(|node| Node::from_json(node).unwrap_or_default())
+                        .collect()
+                });
+
+        let edges =
+            response
+                .get("edges")
+                .and_then(Value::as_array)
+                .map_or_else(Vec::new, |edges| {
+                    edges
+                        .iter()
+                        .filter_map(|edge| Edge::from_json(edge, nodes.len()))
+                        .collect()
+                });
+
+        Self { nodes, edges }
+    }
+
+    /// The direct dependencies of a node, in the order of the response.
+    pub fn dependencies(&self, node: usize) -> impl Iterator<Item = &Edge> + '_ {
+        self.edges.iter().filter(move |edge| edge.from == node)
+    }
+}
+
+impl Edge {
+    fn from_json(edge: &Value, nodes: usize) -> Option<Self> {
+        let index = |key: &str| {
+            let index = edge.get(key)?.as_u64()? as usize;
+            (index < nodes).then_some(index)
+        };
+        Some(Self {
+            from: index("fromNode")?,
+            to: index("toNode")?,
+            requirement: edge
+                .get("requirement")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .into(),
+        })
+    }
+}
+
+impl Node {
+    fn from_json(node: &Value) -> Option<Self> {
+        let (name, version) = match node.get("versionKey") {
+            Some(key) => (key.get("name")?, key.get("version")?),
+            None => (node.get("package")?.get("name")?, node.get("version")?),
+        };
+        Some(Self {
+            name: name.as_str()?.into(),
+            version: version.as_str()?.into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn version_key_nodes() {
+        let response = json!({
+            "nodes": [
+                { "versionKey": { "system": "CARGO", "name": "tokio", "version": "1.21.2" }, "relation": "SELF" },
+                { "versionKey": { "system": "CARGO", "name": "bytes", "version": "1.2.1" }, "relation": "DIRECT" },
+                { "versionKey": { "system": "CARGO", "name": "serde", "version": "1.0.147" }, "relation": "INDIRECT" }
+            ],
+            "edges": [
+                { "fromNode": 0, "toNode": 1, "requirement": "^1.1.0" },
+                { "fromNode": 1, "toNode": 2, "requirement": "^1.0.60" },
+                { "fromNode": 1, "toNode": 7, "requirement": "^1" }
+            ]
+        });
+        let graph = DependencyGraph::from_json(&response);
+        assert_eq!(graph.nodes.len(), 3);
+        assert_eq!(graph.nodes[1].name, "bytes");
+        assert_eq!(graph.edges.len(), 2);
+        let direct = graph.dependencies(0).map(|e| e.to).collect::<Vec<_>>();
+        assert_eq!(direct, vec![1]);
+    }
+
+    #[test]
+    fn package_nodes() {
+        let response = json!({
+            "nodes": [
+                { "package": { "system": "NPM", "name": "neo4j-driver" }, "version": "5.2.0" },
+                { "package": { "system": "NPM", "name": "rxjs" }, "version": "7.5.7" }
+            ],
+            "edges": [{ "fromNode": 0, "toNode": 1 }]
+        });
+        let graph = DependencyGraph::from_json(&response);
+        assert_eq!(
+            graph.nodes[1],
+            Node {
+                name: "rxjs".into(),
+                version: "7.5.7".into()
+            }
+        );
+        assert_eq!(graph.edges[0].requirement, "");
+    }
+}
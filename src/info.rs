@@ -0,0 +1,221 @@
+use crate::Config;
+use color_eyre::eyre::Result;
+use console::style;
+use latest_version::{Client, Coordinates, Requirement, VersionDetails};
+use std::fmt::Write;
+use tokio::io::{self, AsyncWriteExt};
+
+/// Prints the advisories and licenses of the current version and the latest versions, as configured.
+pub(crate) async fn report(
+    client: &Client,
+    config: &Config,
+    coordinates: &Coordinates,
+    current: Option<&str>,
+    latest: &[(Requirement, Option<String>)],
+) -> Result<()> {
+    if config.advisories.is_none() && !config.licenses {
+        return Ok(());
+    }
+
+    let current = match current {
+        Some(version) => Some(client.query_version_details(coordinates, version).await?),
+        None => None,
+    };
+    // the current version is often the latest one as well, its details are not queried twice
+    let mut latest_details = Vec::<VersionDetails>::with_capacity(latest.len());
+    for version in latest.iter().filter_map(|(_, v)| v.as_deref()) {
+        if latest_details.iter().any(|d| d.version == version) {
+            continue;
+        }
+        let details = match &current {
+            Some(current) if current.version == version => current.clone(),
+            _ => client.query_version_details(coordinates, version).await?,
+        };
+        latest_details.push(details);
+    }
+
+    let mut msg = String::with_capacity(128);
+    write_report(
+        &mut msg,
+        config.advisories.is_some(),
+        config.licenses,
+        coordinates,
+        current.as_ref(),
+        &latest_details,
+    );
+    io::stdout().write_all(msg.as_bytes()).await?;
+    Ok(())
+}
+
+fn write_report(
+    msg: &mut String,
+    advisories: bool,
+    licenses: bool,
+    coordinates: &Coordinates,
+    current: Option<&VersionDetails>,
+    latest: &[VersionDetails],
+) {
+    if advisories {
+        if let Some(current) = current {
+            write_advisories(msg, coordinates, "current version", current);
+        }
+        for version in latest.iter().filter(|v| Some(*v) != current) {
+            write_advisories(msg, coordinates, "version", version);
+        }
+    }
+    if licenses {
+        for version in latest {
+            write_licenses(msg, coordinates, current, version);
+        }
+    }
+}
+
+fn write_advisories(
+    msg: &mut String,
+    coordinates: &Coordinates,
+    label: &str,
+    details: &VersionDetails,
+) {
+    if details.advisories.is_empty() {
+        let _ = writeln!(
+            msg,
+            "No known advisories for {} {} of {}:{}",
+            label,
+            style(&details.version).green(),
+            style(coordinates.system_slug()).magenta(),
+            style(coordinates.package_slug()).blue(),
+        );
+        return;
+    }
+
+    let _ = writeln!(
+        msg,
+        "Advisories for {} {} of {}:{}",
+        label,
+        style(&details.version).red().bold(),
+        style(coordinates.system_slug()).magenta(),
+        style(coordinates.package_slug()).blue(),
+    );
+    for advisory in &details.advisories {
+        let _ = write!(msg, "  {}", style(&advisory.id).red());
+        if let Some(severity) = &advisory.severity {
+            let _ = write!(msg, " ({})", style(severity).bold());
+        }
+        if let Some(title) = &advisory.title {
+            let _ = write!(msg, ": {}", title);
+        }
+        msg.push('\n');
+    }
+}
+
+fn write_licenses(
+    msg: &mut String,
+    coordinates: &Coordinates,
+    current: Option<&VersionDetails>,
+    details: &VersionDetails,
+) {
+    let _ = write!(
+        msg,
+        "License of {}:{} {}: {}",
+        style(coordinates.system_slug()).magenta(),
+        style(coordinates.package_slug()).blue(),
+        style(&details.version).green(),
+        style(license_list(&details.licenses)).cyan().bold(),
+    );
+    if let Some(current) = current.filter(|c| c.licenses != details.licenses) {
+        let _ = write!(
+            msg,
+            " {}",
+            style(format!(
+                "(changed from {} in {})",
+                license_list(&current.licenses),
+                current.version
+            ))
+            .yellow()
+            .bold()
+        );
+    }
+    msg.push('\n');
+}
+
+fn license_list(licenses: &[String]) -> String {
+    if licenses.is_empty() {
+        String::from("unknown")
+    } else {
+        licenses.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn details(version: &str, licenses: &[&str]) -> VersionDetails {
+        VersionDetails {
+            version: version.into(),
+            licenses: licenses.iter().map(|l| l.to_string()).collect(),
+            ..VersionDetails::default()
+        }
+    }
+
+    #[test]
+    fn license_changed() {
+        console::set_colors_enabled(false);
+        let mut msg = String::new();
+        write_licenses(
+            &mut msg,
+            &Coordinates::Cargo("foo".into()),
+            Some(&details("1.0.0", &["MIT"])),
+            &details("2.0.0", &["MIT", "Apache-2.0"]),
+        );
+        assert_eq!(
+            msg,
+            "License of cargo:foo 2.0.0: MIT, Apache-2.0 (changed from MIT in 1.0.0)\n"
+        );
+    }
+
+    #[test]
+    fn license_unchanged() {
+        console::set_colors_enabled(false);
+        let mut msg = String::new();
+        write_licenses(
+            &mut msg,
+            &Coordinates::Cargo("foo".into()),
+            Some(&details("1.0.0", &["MIT"])),
+            &details("1.2.0", &["MIT"]),
+        );
+        assert_eq!(msg, "License of cargo:foo 1.2.0: MIT\n");
+    }
+
+    #[test]
+    fn current_is_latest() {
+        console::set_colors_enabled(false);
+        let current = details("1.2.0", &["MIT"]);
+        let mut msg = String::new();
+        write_report(
+            &mut msg,
+            true,
+            true,
+            &Coordinates::Cargo("foo".into()),
+            Some(&current),
+            std::slice::from_ref(&current),
+        );
+        assert_eq!(
+            msg,
+            "No known advisories for current version 1.2.0 of cargo:foo\nLicense of cargo:foo 1.2.0: MIT\n"
+        );
+    }
+
+    #[test]
+    fn unknown_license() {
+        console::set_colors_enabled(false);
+        let mut msg = String::new();
+        write_licenses(
+            &mut msg,
+            &Coordinates::Cargo("foo".into()),
+            None,
+            &details("1.2.0", &[]),
+        );
+        assert_eq!(msg, "License of cargo:foo 1.2.0: unknown\n");
+    }
+}
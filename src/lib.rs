@@ -0,0 +1,49 @@
+//! Check deps.dev for the latest version of artifacts.
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! use latest_version::{parse_coordinates, Client, Credentials};
+//!
+//! let check = parse_coordinates("cargo:serde:^1")?;
+//! let client = Client::new(Credentials::load()?)?;
+//! let versions = client.query_versions(&check.coordinates).await?;
+//! for (requirement, latest) in versions.latest_versions(false, check.versions) {
+//!     println!("{}: {:?}", requirement, latest);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+mod client;
+mod coordinates;
+mod credentials;
+mod declaration;
+mod details;
+mod filter;
+#[cfg(test)]
+mod fixture;
+mod graph;
+mod lockfile;
+mod manifest;
+mod requirement;
+mod scheme;
+mod versions;
+
+pub use client::Client;
+pub use coordinates::{
+    parse_coordinates, parse_coordinates_list, Coordinates, Error, VersionCheck,
+    SEMVER_RANGE_SYNTAX,
+};
+pub use credentials::{Credential, Credentials, Error as CredentialsError};
+pub use declaration::{Declaration, DeclarationFormat};
+pub use details::{Advisory, VersionDetails};
+pub use filter::VersionFilter;
+pub use graph::{DependencyGraph, Edge, Node};
+pub use lockfile::{Error as LockfileError, LockedPackage, Lockfile, LockfileKind};
+pub use manifest::{
+    Dependency, Error as ManifestError, Manifest, ManifestKind, Member as WorkspaceMember,
+    Workspace, WorkspaceDependency,
+};
+pub use requirement::Requirement;
+pub use scheme::VersionScheme;
+pub use versions::{PreReleaseChannel, Release, ReleaseStatus, Versions};
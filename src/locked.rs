@@ -0,0 +1,167 @@
+use crate::{find_latest_versions, info, Config};
+use color_eyre::eyre::Result;
+use console::style;
+use latest_version::{Client, LockedPackage, Lockfile, Requirement};
+use std::{fmt::Write, sync::Arc};
+use tokio::io::{self, AsyncWriteExt};
+
+/// Compares the locked versions to the latest versions that match the requirement and to the latest version overall.
+pub(crate) async fn run(client: Client, config: Config, lockfiles: Vec<Lockfile>) -> Result<()> {
+    let client = Arc::new(client);
+    for lockfile in lockfiles {
+        let tasks = lockfile
+            .packages()
+            .iter()
+            .cloned()
+            .map(|package| {
+                let client = Arc::clone(&client);
+                let config = config.clone();
+                tokio::spawn(async move { latest_versions(&client, &config, &package).await })
+            })
+            .collect::<Vec<_>>();
+
+        let mut results = Vec::with_capacity(tasks.len());
+        for (task, package) in tasks.into_iter().zip(lockfile.packages()) {
+            // a failed lookup is reported for its package, the other packages are still checked
+            let result = task.await?.map_err(|e| e.to_string());
+            results.push((package, result));
+        }
+
+        report(&lockfile, &results).await?;
+
+        for (package, (matching, latest)) in results
+            .iter()
+            .filter_map(|(package, result)| Some((package, result.as_ref().ok()?)))
+        {
+            let latest = [
+                (package.requirement.clone(), matching.clone()),
+                (Requirement::STAR, latest.clone()),
+            ];
+            info::report(
+                &client,
+                &config,
+                &package.coordinates,
+                Some(&package.version),
+                &latest,
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn latest_versions(
+    client: &Client,
+    config: &Config,
+    package: &LockedPackage,
+) -> Result<(Option<String>, Option<String>)> {
+    let coordinates = &package.coordinates;
+    let versions = client.query_versions(coordinates).await?;
+    let (mut matching, latest) = find_latest_versions(
+        client,
+        config,
+        coordinates,
+        versions,
+        vec![package.requirement.clone()],
+    )
+    .await?;
+    Ok((matching.pop().and_then(|(_, latest)| latest), latest))
+}
+
+/// The latest matching and the latest overall version of a package, or why they could not be found.
+type LockedResult<'a> = (
+    &'a LockedPackage,
+    Result<(Option<String>, Option<String>), String>,
+);
+
+async fn report(lockfile: &Lockfile, results: &[LockedResult<'_>]) -> Result<()> {
+    let mut msg = String::with_capacity(256);
+    write_report(&mut msg, lockfile, results);
+    io::stdout().write_all(msg.as_bytes()).await?;
+    Ok(())
+}
+
+fn write_report(msg: &mut String, lockfile: &Lockfile, results: &[LockedResult<'_>]) {
+    let path = lockfile
+        .path()
+        .map_or_else(|| String::from("lockfile"), |p| p.display().to_string());
+
+    let _ = writeln!(msg, "Locked versions in {}", style(&path).bold());
+    for (package, result) in results {
+        let coordinates = &package.coordinates;
+        let _ = write!(
+            msg,
+            "  {}:{} {}",
+            style(coordinates.system_slug()).magenta(),
+            style(coordinates.package_slug()).blue(),
+            style_version(&package.version, &package.version),
+        );
+        if !package.direct {
+            let _ = write!(msg, " {}", style("(transitive)").dim());
+        }
+        let (matching, latest) = match result {
+            Ok(result) => result,
+            Err(error) => {
+                let _ = writeln!(msg, ", {}: {}", style("lookup failed").red(), error);
+                continue;
+            }
+        };
+        let _ = writeln!(
+            msg,
+            ", latest matching {}: {}, latest: {}",
+            style(&package.requirement).cyan(),
+            matching.as_deref().map_or_else(
+                || style("none").yellow().to_string(),
+                |v| style_version(v, &package.version)
+            ),
+            latest.as_deref().map_or_else(
+                || style("none").yellow().to_string(),
+                |v| style_version(v, &package.version)
+            ),
+        );
+    }
+}
+
+/// Highlights versions that differ from the locked one.
+fn style_version(version: &str, locked: &str) -> String {
+    if version == locked {
+        style(version).green().to_string()
+    } else {
+        style(version).yellow().bold().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use latest_version::LockfileKind;
+
+    #[test]
+    fn failed_lookup_is_reported_per_package() {
+        console::set_colors_enabled(false);
+        let lockfile = Lockfile::parse(
+            LockfileKind::Go,
+            "golang.org/x/net v0.1.0 h1:abc=\ngolang.org/x/text v0.3.7 h1:def=\n",
+            None,
+        )
+        .unwrap();
+        let packages = lockfile.packages();
+        let results = [
+            (&packages[0], Err(String::from("connection refused"))),
+            (
+                &packages[1],
+                Ok((Some(String::from("v0.3.8")), Some(String::from("v0.14.0")))),
+            ),
+        ];
+
+        let mut msg = String::new();
+        write_report(&mut msg, &lockfile, &results);
+        assert_eq!(
+            msg,
+            "Locked versions in lockfile\n  \
+             go:golang.org/x/net v0.1.0 (transitive), lookup failed: connection refused\n  \
+             go:golang.org/x/text v0.3.7 (transitive), latest matching ^0.3.7: v0.3.8, latest: v0.14.0\n"
+        );
+    }
+}
@@ -0,0 +1,508 @@
+use crate::Requirement;
+use crate::{Coordinates, Manifest, ManifestError};
+use serde::Deserialize;
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// The kinds of lockfiles that resolved versions can be read from.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockfileKind {
+    /// A `Cargo.lock`
+    Cargo,
+    /// A `package-lock.json`
+    Npm,
+    /// A `go.sum`
+    Go,
+}
+
+/// All packages with the versions they are locked to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lockfile {
+    path: Option<PathBuf>,
+    kind: LockfileKind,
+    packages: Vec<LockedPackage>,
+}
+
+/// A package from a registry and the version it is locked to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LockedPackage {
+    pub coordinates: Coordinates,
+    pub version: String,
+    /// The requirement from the manifest for direct dependencies.
+    /// For transitive dependencies, or if there is no manifest, all versions that are compatible to the locked one.
+    pub requirement: Requirement,
+    /// Whether the package is a dependency in the manifest.
+    pub direct: bool,
+}
+
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum Error {
+    Read(PathBuf, io::Error),
+    UnknownKind(PathBuf),
+    Toml(toml::de::Error),
+    Json(serde_json::Error),
+    Manifest(ManifestError),
+}
+
+impl LockfileKind {
+    /// Detects the kind from a file name like `Cargo.lock`.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.file_name()?.to_str()? {
+            "Cargo.lock" => Some(Self::Cargo),
+            "package-lock.json" | "npm-shrinkwrap.json" => Some(Self::Npm),
+            "go.sum" => Some(Self::Go),
+            _ => None,
+        }
+    }
+
+    fn file_names() -> &'static [&'static str] {
+        &["Cargo.lock", "package-lock.json", "go.sum"]
+    }
+
+    fn manifest_name(self) -> Option<&'static str> {
+        match self {
+            Self::Cargo => Some("Cargo.toml"),
+            Self::Npm => Some("package.json"),
+            Self::Go => None,
+        }
+    }
+}
+
+impl Lockfile {
+    /// Reads the lockfile at `path` and the requirements from the manifest next to it.
+    ///
+    /// If `path` is a directory, the first lockfile found in there is read.
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let mut path = path.as_ref().to_path_buf();
+        if path.is_dir() {
+            path = LockfileKind::file_names()
+                .iter()
+                .map(|name| path.join(name))
+                .find(|file| file.is_file())
+                .ok_or(Error::UnknownKind(path))?;
+        }
+
+        let kind = match LockfileKind::from_path(&path) {
+            Some(kind) => kind,
+            None => return Err(Error::UnknownKind(path)),
+        };
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) => return Err(Error::Read(path, e)),
+        };
+
+        let manifest = kind
+            .manifest_name()
+            .and_then(|name| Some(path.parent()?.join(name)))
+            .filter(|manifest| manifest.is_file())
+            .map(Manifest::read)
+            .transpose()
+            .map_err(Error::Manifest)?;
+
+        let mut lockfile = Self::parse(kind, &content, manifest.as_ref())?;
+        lockfile.path = Some(path);
+        Ok(lockfile)
+    }
+
+    /// Parses the content of a lockfile, taking the requirements of direct dependencies from `manifest`.
+    pub fn parse(
+        kind: LockfileKind,
+        content: &str,
+        manifest: Option<&Manifest>,
+    ) -> Result<Self, Error> {
+        let locked = match kind {
+            LockfileKind::Cargo => cargo_packages(content)?,
+            LockfileKind::Npm => npm_packages(content)?,
+            LockfileKind::Go => go_packages(content),
+        };
+
+        let packages = locked
+            .into_iter()
+            .filter_map(|(coordinates, version)| {
+                let locked = lenient_semver::parse(&version).ok();
+                let direct = manifest.and_then(|m| {
+                    m.dependencies().iter().find(|dep| {
+                        dep.coordinates == coordinates
+                            && locked.as_ref().is_some_and(|v| dep.requirement.matches(v))
+                    })
+                });
+                let requirement = match direct {
+                    Some(dep) => dep.requirement.clone(),
+                    None => compatible_requirement(&version)?,
+                };
+                Some(LockedPackage {
+                    coordinates,
+                    version,
+                    requirement,
+                    direct: direct.is_some(),
+                })
+            })
+            .collect();
+
+        Ok(Self {
+            path: None,
+            kind,
+            packages,
+        })
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub fn kind(&self) -> LockfileKind {
+        self.kind
+    }
+
+    /// All locked packages, direct and transitive, sorted by name.
+    pub fn packages(&self) -> &[LockedPackage] {
+        &self.packages
+    }
+}
+
+fn compatible_requirement(version: &str) -> Option<Requirement> {
+    let version = lenient_semver::parse(version).ok()?;
+    Requirement::parse(&format!(
+        "^{}.{}.{}",
+        version.major, version.minor, version.patch
+    ))
+    .ok()
+}
+
+fn cargo_packages(content: &str) -> Result<Vec<(Coordinates, String)>, Error> {
+    #[derive(Deserialize)]
+    struct CargoLock {
+        #[serde(default)]
+        package: Vec<Package>,
+    }
+
+    #[derive(Deserialize)]
+    struct Package {
+        name: String,
+        version: String,
+        source: Option<String>,
+    }
+
+    let lock = toml::from_str::<CargoLock>(content).map_err(Error::Toml)?;
+    let packages = lock
+        .package
+        .into_iter()
+        // path dependencies have no source, git dependencies are not in the registry
+        .filter(|p| {
+            p.source
+                .as_deref()
+                .is_some_and(|s| s.starts_with("registry+") || s.starts_with("sparse+"))
+        })
+        .map(|p| (Coordinates::Cargo(p.name), p.version))
+        .collect();
+
+    Ok(into_sorted(packages))
+}
+
+fn npm_packages(content: &str) -> Result<Vec<(Coordinates, String)>, Error> {
+    let lock = serde_json::from_str::<Value>(content).map_err(Error::Json)?;
+    let mut packages = Vec::new();
+
+    if let Some(entries) = lock.get("packages").and_then(Value::as_object) {
+        // lockfileVersion 2 and 3
+        for (path, entry) in entries {
+            let name = match path.rsplit_once("node_modules/") {
+                Some((_, name)) => name,
+                None => continue,
+            };
+            if entry.get("link").and_then(Value::as_bool) == Some(true) {
+                continue;
+            }
+            if let Some(version) = entry.get("version").and_then(Value::as_str) {
+                packages.push((npm_coordinates(name), version.to_string()));
+            }
+        }
+    } else if let Some(dependencies) = lock.get("dependencies") {
+        // lockfileVersion 1
+        fn collect(dependencies: &Value, packages: &mut Vec<(Coordinates, String)>) {
+            for (name, entry) in dependencies.as_object().into_iter().flatten() {
+                if let Some(version) = entry.get("version").and_then(Value::as_str) {
+                    if !version.contains(':') {
+                        packages.push((npm_coordinates(name), version.to_string()));
+                    }
+                }
+                if let Some(nested) = entry.get("dependencies") {
+                    collect(nested, packages);
+                }
+            }
+        }
+        collect(dependencies, &mut packages);
+    }
+
+    Ok(into_sorted(packages))
+}
+
+fn npm_coordinates(name: &str) -> Coordinates {
+    match name.strip_prefix('@').and_then(|n| n.split_once('/')) {
+        Some((scope, package)) => Coordinates::Npm {
+            scope: Some(scope.into()),
+            package: package.into(),
+        },
+        None => Coordinates::Npm {
+            scope: None,
+            package: name.into(),
+        },
+    }
+}
+
+/// go.sum has a `/go.mod` hash for every version that was considered for the module graph,
+/// but the hash of the full module only for the versions in the build, of which the build uses the highest one.
+/// Modules that only have `/go.mod` lines use the highest of those.
+fn go_packages(content: &str) -> Vec<(Coordinates, String)> {
+    let mut built = HashMap::<&str, (semver::Version, &str)>::new();
+    let mut considered = HashMap::<&str, (semver::Version, &str)>::new();
+    for line in content.lines() {
+        let mut parts = line.split_whitespace();
+        let (module, version) = match (parts.next(), parts.next()) {
+            (Some(module), Some(version)) => (module, version),
+            _ => continue,
+        };
+        let (version, packages) = match version.strip_suffix("/go.mod") {
+            Some(version) => (version, &mut considered),
+            None => (version, &mut built),
+        };
+        let parsed = match lenient_semver::parse(version) {
+            Ok(parsed) => parsed,
+            Err(_) => continue,
+        };
+        match packages.get(module) {
+            Some((existing, _)) if *existing >= parsed => {}
+            _ => {
+                let _ = packages.insert(module, (parsed, version));
+            }
+        }
+    }
+    for (module, version) in considered {
+        let _ = built.entry(module).or_insert(version);
+    }
+
+    into_sorted(
+        built
+            .into_iter()
+            .map(|(module, (_, version))| (Coordinates::AnyGo(module.into()), version.into()))
+            .collect(),
+    )
+}
+
+/// Sorts by name and version, a package can be locked in multiple versions.
+fn into_sorted(mut packages: Vec<(Coordinates, String)>) -> Vec<(Coordinates, String)> {
+    packages.sort_by(|(lhs, lhs_version), (rhs, rhs_version)| {
+        (lhs.package_slug(), lhs_version).cmp(&(rhs.package_slug(), rhs_version))
+    });
+    packages.dedup();
+    packages
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Read(path, _) => write!(f, "Could not read {}", path.display()),
+            Error::UnknownKind(path) => write!(
+                f,
+                "Unknown lockfile {}, expected one of {}",
+                path.display(),
+                LockfileKind::file_names().join(", ")
+            ),
+            Error::Toml(e) => write!(f, "Invalid TOML: {}", e),
+            Error::Json(e) => write!(f, "Invalid JSON: {}", e),
+            Error::Manifest(e) => write!(f, "Invalid manifest: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Read(_, src) => Some(src),
+            Error::UnknownKind(_) => None,
+            Error::Toml(src) => Some(src),
+            Error::Json(src) => Some(src),
+            Error::Manifest(src) => Some(src),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ManifestKind;
+
+    fn summary(lockfile: &Lockfile) -> Vec<(String, &str, String, bool)> {
+        lockfile
+            .packages()
+            .iter()
+            .map(|p| {
+                (
+                    p.coordinates.package_slug().into_owned(),
+                    p.version.as_str(),
+                    p.requirement.to_string(),
+                    p.direct,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn cargo_lock() {
+        let manifest = Manifest::parse(
+            ManifestKind::Cargo,
+            String::from("[dependencies]\nserde = \"1.0.100\"\nsyn = \"2\"\n"),
+        )
+        .unwrap();
+        let lockfile = Lockfile::parse(
+            LockfileKind::Cargo,
+            r#"
+version = 3
+
+[[package]]
+name = "example"
+version = "0.1.0"
+dependencies = ["serde"]
+
+[[package]]
+name = "serde"
+version = "1.0.147"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+dependencies = ["serde_derive"]
+
+[[package]]
+name = "serde_derive"
+version = "1.0.147"
+source = "sparse+https://index.crates.io/"
+
+[[package]]
+name = "syn"
+version = "1.0.109"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "syn"
+version = "2.0.15"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "forked"
+version = "0.1.0"
+source = "git+https://github.com/user/forked#abcdef"
+"#,
+            Some(&manifest),
+        )
+        .unwrap();
+
+        assert_eq!(
+            summary(&lockfile),
+            vec![
+                ("serde".into(), "1.0.147", "^1.0.100".into(), true),
+                ("serde_derive".into(), "1.0.147", "^1.0.147".into(), false),
+                ("syn".into(), "1.0.109", "^1.0.109".into(), false),
+                ("syn".into(), "2.0.15", "^2".into(), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn package_lock_v3() {
+        let lockfile = Lockfile::parse(
+            LockfileKind::Npm,
+            r#"{
+  "name": "example",
+  "lockfileVersion": 3,
+  "packages": {
+    "": { "name": "example", "dependencies": { "neo4j-driver": "^4.3.0" } },
+    "node_modules/neo4j-driver": { "version": "4.3.4" },
+    "node_modules/neo4j-driver/node_modules/rxjs": { "version": "6.6.7" },
+    "node_modules/@types/node": { "version": "18.11.9" },
+    "node_modules/local": { "resolved": "../local", "link": true }
+  }
+}"#,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            summary(&lockfile),
+            vec![
+                ("@types/node".into(), "18.11.9", "^18.11.9".into(), false),
+                ("neo4j-driver".into(), "4.3.4", "^4.3.4".into(), false),
+                ("rxjs".into(), "6.6.7", "^6.6.7".into(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn package_lock_v1() {
+        let lockfile = Lockfile::parse(
+            LockfileKind::Npm,
+            r#"{
+  "lockfileVersion": 1,
+  "dependencies": {
+    "neo4j-driver": {
+      "version": "4.3.4",
+      "dependencies": { "rxjs": { "version": "6.6.7" } }
+    },
+    "local": { "version": "file:../local" }
+  }
+}"#,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            summary(&lockfile),
+            vec![
+                ("neo4j-driver".into(), "4.3.4", "^4.3.4".into(), false),
+                ("rxjs".into(), "6.6.7", "^6.6.7".into(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn go_sum() {
+        let lockfile = Lockfile::parse(
+            LockfileKind::Go,
+            "github.com/neo4j/neo4j-go-driver v1.8.2/go.mod h1:abc=\n\
+             github.com/neo4j/neo4j-go-driver v1.8.3 h1:def=\n\
+             github.com/neo4j/neo4j-go-driver v1.8.3/go.mod h1:ghi=\n\
+             github.com/neo4j/neo4j-go-driver v1.9.0/go.mod h1:mno=\n\
+             golang.org/x/text v0.3.7 h1:jkl=\n\
+             golang.org/x/net v0.1.0/go.mod h1:pqr=\n\
+             golang.org/x/net v0.2.0/go.mod h1:stu=\n",
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            summary(&lockfile),
+            vec![
+                (
+                    "github.com/neo4j/neo4j-go-driver".into(),
+                    "v1.8.3",
+                    "^1.8.3".into(),
+                    false
+                ),
+                ("golang.org/x/net".into(), "v0.2.0", "^0.2.0".into(), false),
+                ("golang.org/x/text".into(), "v0.3.7", "^0.3.7".into(), false),
+            ]
+        );
+    }
+
+    #[test_case::test_case("Cargo.lock", Some(LockfileKind::Cargo); "cargo")]
+    #[test_case::test_case("package-lock.json", Some(LockfileKind::Npm); "npm")]
+    #[test_case::test_case("go.sum", Some(LockfileKind::Go); "go")]
+    #[test_case::test_case("yarn.lock", None; "unknown")]
+    fn test_kind_from_path(path: &str, expected: Option<LockfileKind>) {
+        assert_eq!(LockfileKind::from_path(Path::new(path)), expected);
+    }
+}
@@ -1,14 +1,26 @@
 use color_eyre::eyre::Result;
 use console::style;
-use reqwest::{Client, Url};
-use semver::VersionReq;
-use serde_json::Value;
-use std::{borrow::Cow, fmt::Write, sync::Arc, time::Duration};
+use latest_version::{
+    Client, Coordinates, Credentials, PreReleaseChannel, Requirement, VersionCheck, VersionFilter,
+    Versions,
+};
+use std::{fmt::Write, sync::Arc};
 use tokio::io::{self, AsyncWriteExt};
-use versions::Versions;
 
+mod advisories;
+mod config;
+mod deps;
+mod diagnostics;
+mod diff;
+#[cfg(test)]
+mod fixture;
+mod info;
+mod locked;
 mod opts;
-mod versions;
+mod suggest;
+mod update;
+mod watch;
+mod workspace;
 
 fn main() -> Result<()> {
     if console::colors_enabled() {
@@ -17,26 +29,43 @@ fn main() -> Result<()> {
             .install()?
     }
 
-    let opts = opts::Opts::new();
+    let opts = opts::Opts::new()?;
     let config = opts.config();
+    let client = opts.registries().iter().fold(
+        Client::new(Credentials::load()?)?,
+        |client, (system, url)| client.with_registry(system, url.clone()),
+    );
+    let watch = opts.watch();
+    let update = opts.update();
+    let manifests = opts.manifests().to_vec();
+    let lockfiles = opts.lockfiles().to_vec();
+    let workspaces = opts.workspaces().to_vec();
+    let diff = opts.diff();
     let checks = opts.into_version_checks();
 
     tokio::runtime::Builder::new_current_thread()
         .enable_all()
         .build()?
-        .block_on(async move { run(config, checks).await })
+        .block_on(async move {
+            if let Some(diff) = diff {
+                return diff::run(client, config, diff).await;
+            }
+            match (watch, update) {
+                (Some(watch), _) => watch::run(client, config, checks, watch).await,
+                (None, Some(update)) => update::run(client, config, manifests, update).await,
+                (None, None) if lockfiles.is_empty() && workspaces.is_empty() => {
+                    run(client, config, checks).await
+                }
+                (None, None) => {
+                    run(client.clone(), config.clone(), checks).await?;
+                    locked::run(client.clone(), config.clone(), lockfiles).await?;
+                    workspace::run(client, config, workspaces).await
+                }
+            }
+        })
 }
 
-async fn run(config: Config, checks: Vec<VersionCheck>) -> Result<()> {
-    static APP_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
-
-    let client = Client::builder()
-        .user_agent(APP_USER_AGENT)
-        .gzip(true)
-        .timeout(Duration::from_secs(10))
-        .tcp_keepalive(Some(Duration::from_secs(60)))
-        .use_rustls_tls()
-        .build()?;
+async fn run(client: Client, config: Config, checks: Vec<VersionCheck>) -> Result<()> {
     let client = Arc::new(client);
 
     let tasks = checks
@@ -44,6 +73,7 @@ async fn run(config: Config, checks: Vec<VersionCheck>) -> Result<()> {
         .map({
             |check| {
                 let client = Arc::clone(&client);
+                let config = config.clone();
                 tokio::spawn(async move { run_check_and_report(client, config, check).await })
             }
         })
@@ -60,34 +90,118 @@ async fn run_check_and_report(
     config: Config,
     check: VersionCheck,
 ) -> Result<()> {
-    let url = check_url(&check);
-    let versions = query_versions(&*client, url.clone()).await?;
-    let versions = versions.latest_versions(config.include_pre_releases, check.versions);
+    let (versions, overall) = latest_versions(&client, &config, &check).await?;
+    report(&check, &versions, overall.as_deref()).await?;
+    if config.suggest {
+        suggest::report(&check.coordinates, &versions).await?;
+    }
+    info::report(
+        &client,
+        &config,
+        &check.coordinates,
+        check.current.as_deref(),
+        &versions,
+    )
+    .await?;
+    if let Some(deps) = config.deps {
+        deps::report(&client, &check.coordinates, &versions, deps).await?;
+    }
+    Ok(())
+}
 
+async fn latest_versions(
+    client: &Client,
+    config: &Config,
+    check: &VersionCheck,
+) -> Result<(Vec<(Requirement, Option<String>)>, Option<String>)> {
+    let mut versions = client
+        .query_versions(&check.coordinates)
+        .await?
+        .filter(&check.filter)
+        .scheme(check.scheme);
+    if let Some(within) = check.released_within {
+        versions = versions.released_within(within);
+    }
+    find_latest_versions(
+        client,
+        config,
+        &check.coordinates,
+        versions,
+        check.versions.clone(),
+    )
+    .await
+}
+
+/// Finds the latest version for each requirement and the latest version overall,
+/// applying the withdrawn, pre release, version filter, and advisory options of the config.
+///
+/// With diagnostics, the versions that cannot be parsed are reported first.
+async fn find_latest_versions(
+    client: &Client,
+    config: &Config,
+    coordinates: &Coordinates,
+    versions: Versions,
+    requirements: Vec<Requirement>,
+) -> Result<(Vec<(Requirement, Option<String>)>, Option<String>)> {
+    let mut versions = versions
+        .include_withdrawn(config.include_yanked)
+        .pre_release_channel(config.pre_release_channel)
+        .natural_fallback(config.natural_fallback);
+    if let Some(filter) = &config.version_filter {
+        versions = versions.filter(filter);
+    }
+    if config.diagnostics {
+        diagnostics::report(coordinates, &versions, config.natural_fallback).await?;
+    }
+    advisories::latest_versions(client, config, coordinates, versions, requirements).await
+}
+
+/// Prints the latest version per requirement, noting if the latest version overall is a newer major version.
+async fn report(
+    check: &VersionCheck,
+    versions: &[(Requirement, Option<String>)],
+    overall: Option<&str>,
+) -> Result<()> {
     let mut stdout = io::stdout();
     let mut msg = String::with_capacity(64);
-    let coordinates = check.coordinates;
+    let coordinates = &check.coordinates;
     let pkg = coordinates.package_slug();
+    // a version catalog alias is what the user knows the dependency by
+    let alias = check
+        .name
+        .as_ref()
+        .map(|name| format!(" ({})", style(name).bold()))
+        .unwrap_or_default();
 
     for (req, latest) in versions {
         msg.clear();
         let _ = match latest {
-            Some(latest) => writeln!(
+            Some(latest) => write!(
                 msg,
-                "Latest version for {}:{} matching {}: {}",
+                "Latest version for {}:{}{} matching {}: {}",
                 style(coordinates.system_slug()).magenta(),
                 style(&pkg).blue(),
+                alias,
                 style(req).cyan().bold(),
                 style(latest).green().bold()
             ),
-            None => writeln!(
+            None => write!(
                 msg,
-                "No version for {}:{} matching {}",
+                "No version for {}:{}{} matching {}",
                 style(coordinates.system_slug()).magenta(),
                 style(&pkg).blue(),
+                alias,
                 style(req).yellow().bold()
             ),
         };
+        if let Some(overall) = overall.filter(|o| is_newer_major(latest.as_deref(), o)) {
+            let _ = write!(
+                msg,
+                ", newer major available: {}",
+                style(overall).yellow().bold()
+            );
+        }
+        msg.push('\n');
 
         stdout.write_all(msg.as_bytes()).await?;
     }
@@ -95,90 +209,30 @@ async fn run_check_and_report(
     Ok(())
 }
 
-fn check_url(check: &VersionCheck) -> Url {
-    let mut url = Url::parse("https://deps.dev/_/s").expect("this is a valid url");
-    url.path_segments_mut().expect("url can be a base").extend([
-        check.coordinates.system_slug(),
-        "p",
-        &check.coordinates.package_slug(),
-        "versions",
-    ]);
-    url
-}
-
-async fn query_versions(client: &Client, url: Url) -> Result<Versions> {
-    let response = client.get(url).send().await?.json::<Value>().await?;
-    let versions = response
-        .get("versions")
-        .and_then(|v| v.as_array())
-        .map_or_else(Versions::default, |v| {
-            v.iter()
-                .filter_map(|v| v.get("version"))
-                .filter_map(|v| v.as_str())
-                .map(String::from)
-                .collect()
-        });
-    Ok(versions)
+/// Whether `overall` is not compatible to and newer than `latest`, or there is no `latest` at all.
+fn is_newer_major(latest: Option<&str>, overall: &str) -> bool {
+    let overall = match lenient_semver::parse(overall) {
+        Ok(overall) => overall,
+        Err(_) => return false,
+    };
+    let latest = match latest.map(lenient_semver::parse) {
+        Some(Ok(latest)) => latest,
+        Some(Err(_)) => return false,
+        None => return true,
+    };
+    overall > latest && diff::is_breaking(&latest, &overall)
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 struct Config {
     include_pre_releases: bool,
-}
-
-#[derive(Debug, Clone, PartialEq)]
-struct VersionCheck {
-    coordinates: Coordinates,
-    versions: Vec<VersionReq>,
-}
-
-#[derive(Debug, Clone, PartialEq)]
-enum Coordinates {
-    Maven {
-        group_id: String,
-        artifact_id: String,
-    },
-    Cargo(String),
-    Npm {
-        scope: Option<String>,
-        package: String,
-    },
-    Go {
-        user: String,
-        module: String,
-    },
-    AnyGo(String),
-}
-
-impl Coordinates {
-    fn system_slug(&self) -> &str {
-        match self {
-            Coordinates::Maven { .. } => "maven",
-            Coordinates::Cargo(_) => "cargo",
-            Coordinates::Npm { .. } => "npm",
-            Coordinates::Go { .. } | Coordinates::AnyGo(_) => "go",
-        }
-    }
-
-    fn package_slug(&self) -> Cow<str> {
-        match self {
-            Coordinates::Maven {
-                group_id,
-                artifact_id,
-            } => Cow::Owned(format!("{}:{}", group_id, artifact_id)),
-            Coordinates::Cargo(package) => Cow::Borrowed(package),
-            Coordinates::Npm {
-                scope: Some(scope),
-                package,
-            } => Cow::Owned(format!("@{}/{}", scope, package)),
-            Coordinates::Npm {
-                scope: None,
-                package,
-            } => Cow::Borrowed(package),
-            Coordinates::Go { user, module } => {
-                Cow::Owned(format!("github.com/{}/{}", user, module))
-            }
-            Coordinates::AnyGo(go) => Cow::Borrowed(go),
-        }
-    }
+    pre_release_channel: Option<PreReleaseChannel>,
+    version_filter: Option<Arc<VersionFilter>>,
+    include_yanked: bool,
+    advisories: Option<advisories::Advisories>,
+    licenses: bool,
+    deps: Option<deps::Deps>,
+    suggest: bool,
+    diagnostics: bool,
+    natural_fallback: bool,
 }
@@ -0,0 +1,385 @@
+use crate::Coordinates;
+use crate::Requirement;
+use std::{
+    fmt::Display,
+    fs, io,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+mod cargo;
+mod gradle;
+mod maven;
+mod npm;
+mod workspace;
+
+pub use workspace::{Member, Workspace, WorkspaceDependency};
+
+/// The kinds of manifest files that dependencies can be read from.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestKind {
+    /// A `Cargo.toml`
+    Cargo,
+    /// A `package.json`
+    Npm,
+    /// A `pom.xml`
+    Maven,
+    /// A `build.gradle` or `build.gradle.kts`
+    Gradle,
+    /// A Gradle version catalog, usually `gradle/libs.versions.toml`
+    GradleCatalog,
+}
+
+/// The dependencies declared in a manifest file, together with their location in the file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Manifest {
+    path: Option<PathBuf>,
+    kind: ManifestKind,
+    content: String,
+    dependencies: Vec<Dependency>,
+}
+
+/// A dependency on a package in a registry, as declared in a manifest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dependency {
+    /// The name of the dependency in the manifest, which might differ from the package name.
+    pub name: String,
+    pub coordinates: Coordinates,
+    /// The versions that are allowed by the manifest.
+    pub requirement: Requirement,
+    raw: String,
+    span: Range<usize>,
+    pinned: bool,
+}
+
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum Error {
+    Read(PathBuf, io::Error),
+    UnknownKind(PathBuf),
+    Toml(toml_edit::TomlError),
+    Json(serde_json::Error),
+    Xml(roxmltree::Error),
+    Glob(glob::PatternError),
+}
+
+impl ManifestKind {
+    /// Detects the kind from a file name like `Cargo.toml`.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.file_name()?.to_str()? {
+            "Cargo.toml" => Some(Self::Cargo),
+            "package.json" => Some(Self::Npm),
+            "pom.xml" => Some(Self::Maven),
+            name if name.ends_with(".pom") => Some(Self::Maven),
+            "build.gradle" | "build.gradle.kts" => Some(Self::Gradle),
+            name if name.ends_with(".versions.toml") => Some(Self::GradleCatalog),
+            _ => None,
+        }
+    }
+
+    fn file_names() -> &'static [&'static str] {
+        &[
+            "Cargo.toml",
+            "package.json",
+            "pom.xml",
+            "build.gradle.kts",
+            "build.gradle",
+            "gradle/libs.versions.toml",
+        ]
+    }
+}
+
+impl Manifest {
+    /// Reads the manifest at `path`.
+    ///
+    /// If `path` is a directory, the first manifest file found in there is read.
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let mut path = path.as_ref().to_path_buf();
+        if path.is_dir() {
+            path = ManifestKind::file_names()
+                .iter()
+                .map(|name| path.join(name))
+                .find(|file| file.is_file())
+                .ok_or(Error::UnknownKind(path))?;
+        }
+
+        let kind = match ManifestKind::from_path(&path) {
+            Some(kind) => kind,
+            None => return Err(Error::UnknownKind(path)),
+        };
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) => return Err(Error::Read(path, e)),
+        };
+
+        let mut manifest = Self::parse(kind, content)?;
+        manifest.path = Some(path);
+        Ok(manifest)
+    }
+
+    /// Parses the content of a manifest.
+    pub fn parse(kind: ManifestKind, content: String) -> Result<Self, Error> {
+        let dependencies = match kind {
+            ManifestKind::Cargo => cargo::dependencies(&content)?,
+            ManifestKind::Npm => npm::dependencies(&content)?,
+            ManifestKind::Maven => maven::dependencies(&content)?,
+            ManifestKind::Gradle => gradle::dependencies(&content)?,
+            ManifestKind::GradleCatalog => gradle::catalog_dependencies(&content)?,
+        };
+        Ok(Self {
+            path: None,
+            kind,
+            content,
+            dependencies,
+        })
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub fn kind(&self) -> ManifestKind {
+        self.kind
+    }
+
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// All dependencies in the order they are declared in the manifest.
+    ///
+    /// Dependencies that are not from a registry or with requirements that cannot be parsed are skipped.
+    pub fn dependencies(&self) -> &[Dependency] {
+        &self.dependencies
+    }
+
+    /// Returns the content of the manifest with the requirements of the given dependencies replaced.
+    ///
+    /// Everything else in the file, including formatting and comments, is left untouched.
+    pub fn with_requirements<'a>(
+        &self,
+        updates: impl IntoIterator<Item = (&'a Dependency, &'a str)>,
+    ) -> String {
+        let mut updates = updates
+            .into_iter()
+            .map(|(dep, requirement)| (dep.span.clone(), requirement))
+            .collect::<Vec<_>>();
+        updates.sort_by_key(|(span, _)| std::cmp::Reverse(span.start));
+        updates.dedup_by_key(|(span, _)| span.start);
+
+        let mut content = self.content.clone();
+        for (span, requirement) in updates {
+            content.replace_range(span, requirement);
+        }
+        content
+    }
+}
+
+impl Dependency {
+    fn new(
+        name: impl Into<String>,
+        coordinates: Coordinates,
+        raw: &str,
+        span: Range<usize>,
+        pinned: bool,
+    ) -> Option<Self> {
+        let requirement = if pinned {
+            let version = lenient_semver::parse(raw).ok()?;
+            Requirement::parse(&format!(
+                "^{}.{}.{}",
+                version.major, version.minor, version.patch
+            ))
+            .ok()?
+            .with_variant(crate::versions::variant(&version).map(String::from))
+        } else {
+            crate::coordinates::parse_version(&coordinates, raw).ok()?
+        };
+
+        Some(Self {
+            name: name.into(),
+            coordinates,
+            requirement,
+            raw: raw.into(),
+            span,
+            pinned,
+        })
+    }
+
+    /// The requirement as it is written in the manifest.
+    pub fn raw_requirement(&self) -> &str {
+        &self.raw
+    }
+
+    /// The lowest version allowed by the requirement, which is the version that is declared.
+    ///
+    /// Returns `None` if the requirement is too complex, e.g. `>=1.2, <1.5`.
+    pub fn current_version(&self) -> Option<String> {
+        if self.pinned {
+            return Some(self.raw.clone());
+        }
+        let (_, current) = self.split_requirement()?;
+        Some(lenient_semver::parse(current).ok()?.to_string())
+    }
+
+    /// The requirement that allows `version` as its lowest version, in the same style as the current one.
+    ///
+    /// Returns `None` if `version` is not newer than the current requirement,
+    /// or if the requirement is too complex to be rewritten, e.g. `>=1.2, <1.5`.
+    pub fn bumped_requirement(&self, version: &str) -> Option<String> {
+        let new = lenient_semver::parse(version).ok()?;
+
+        if self.pinned {
+            let current = lenient_semver::parse(&self.raw).ok()?;
+            return (new > current).then(|| version.to_string());
+        }
+
+        let (op, current) = self.split_requirement()?;
+        let current = lenient_semver::parse(current).ok()?;
+        (new > current).then(|| format!("{}{}", op, version))
+    }
+
+    /// Splits a simple requirement like `^1.2` into its operator and version.
+    fn split_requirement(&self) -> Option<(&str, &str)> {
+        let raw = self.raw.trim();
+        let (op, current) = raw.split_at(raw.find(|c: char| c.is_ascii_digit())?);
+        if !matches!(op.trim(), "" | "^" | "~" | "=" | ">=") {
+            return None;
+        }
+        let is_complex = current.contains([',', ' ', '|', '<', '>'])
+            || current
+                .split('.')
+                .any(|part| matches!(part, "*" | "x" | "X"));
+        (!is_complex).then_some((op, current))
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Read(path, _) => write!(f, "Could not read {}", path.display()),
+            Error::UnknownKind(path) => write!(
+                f,
+                "Unknown manifest {}, expected one of {}",
+                path.display(),
+                ManifestKind::file_names().join(", ")
+            ),
+            Error::Toml(e) => write!(f, "Invalid TOML: {}", e),
+            Error::Json(e) => write!(f, "Invalid JSON: {}", e),
+            Error::Xml(e) => write!(f, "Invalid XML: {}", e),
+            Error::Glob(e) => write!(f, "Invalid workspace member pattern: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Read(_, src) => Some(src),
+            Error::UnknownKind(_) => None,
+            Error::Toml(src) => Some(src),
+            Error::Json(src) => Some(src),
+            Error::Xml(src) => Some(src),
+            Error::Glob(src) => Some(src),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn dependency(raw: &str, pinned: bool) -> Dependency {
+        Dependency::new(
+            "foo",
+            Coordinates::Cargo("foo".into()),
+            raw,
+            0..raw.len(),
+            pinned,
+        )
+        .unwrap()
+    }
+
+    #[test_case("1.0.100", "1.0.150", Some("1.0.150"); "bare")]
+    #[test_case("^1.0", "1.2.0", Some("^1.2.0"); "caret")]
+    #[test_case("~1.2.3", "1.2.9", Some("~1.2.9"); "tilde")]
+    #[test_case("=1.2.3", "2.0.0", Some("=2.0.0"); "exact")]
+    #[test_case(">=1.2", "1.5.0", Some(">=1.5.0"); "gte")]
+    #[test_case("1.2.3", "1.2.3", None; "same version")]
+    #[test_case("1.2", "1.2.0", None; "same version with less precision")]
+    #[test_case("1.5.0", "1.2.0", None; "older version")]
+    #[test_case(">=1.2, <1.5", "1.4.0", None; "multiple comparators")]
+    #[test_case("1.x", "1.4.0", None; "wildcard")]
+    #[test_case("*", "1.4.0", None; "star")]
+    #[test_case("<2", "1.4.0", None; "upper bound")]
+    fn test_bumped_requirement(raw: &str, version: &str, expected: Option<&str>) {
+        assert_eq!(
+            dependency(raw, false)
+                .bumped_requirement(version)
+                .as_deref(),
+            expected
+        );
+    }
+
+    #[test_case("1.2.3", "1.3.0", Some("1.3.0"); "newer")]
+    #[test_case("31.0-jre", "31.1-jre", Some("31.1-jre"); "variant")]
+    #[test_case("1.2.3", "1.2.3", None; "same")]
+    fn test_bumped_pinned_requirement(raw: &str, version: &str, expected: Option<&str>) {
+        assert_eq!(
+            dependency(raw, true).bumped_requirement(version).as_deref(),
+            expected
+        );
+    }
+
+    #[test_case("^1.0", false, Some("1.0.0"); "caret")]
+    #[test_case("~2.3.4", false, Some("2.3.4"); "tilde")]
+    #[test_case(">=1.2, <1.5", false, None; "multiple comparators")]
+    #[test_case("31.1-jre", true, Some("31.1-jre"); "pinned")]
+    fn test_current_version(raw: &str, pinned: bool, expected: Option<&str>) {
+        assert_eq!(
+            dependency(raw, pinned).current_version().as_deref(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_pinned_requirement_is_compatible_range() {
+        assert_eq!(
+            dependency("1.2", true).requirement,
+            Requirement::parse("^1.2.0").unwrap()
+        );
+        assert_eq!(
+            dependency("31.1-jre", true).requirement,
+            Requirement::parse("^31.1.0@jre").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_with_requirements() {
+        let manifest = Manifest::parse(
+            ManifestKind::Cargo,
+            String::from(
+                "[dependencies]\nfoo = \"1.0\" # keep me\nbar = { version = \"0.2\", features = [\"x\"] }\n",
+            ),
+        )
+        .unwrap();
+        let deps = manifest.dependencies();
+        let content = manifest.with_requirements(vec![(&deps[0], "1.4.2"), (&deps[1], "0.3.0")]);
+        assert_eq!(
+            content,
+            "[dependencies]\nfoo = \"1.4.2\" # keep me\nbar = { version = \"0.3.0\", features = [\"x\"] }\n"
+        );
+    }
+
+    #[test_case("Cargo.toml", Some(ManifestKind::Cargo); "cargo")]
+    #[test_case("some/dir/package.json", Some(ManifestKind::Npm); "npm")]
+    #[test_case("pom.xml", Some(ManifestKind::Maven); "maven")]
+    #[test_case("guava-31.1-jre.pom", Some(ManifestKind::Maven); "maven pom file")]
+    #[test_case("build.gradle.kts", Some(ManifestKind::Gradle); "gradle kotlin")]
+    #[test_case("gradle/libs.versions.toml", Some(ManifestKind::GradleCatalog); "gradle catalog")]
+    #[test_case("Cargo.lock", None; "unknown")]
+    fn test_kind_from_path(path: &str, expected: Option<ManifestKind>) {
+        assert_eq!(ManifestKind::from_path(Path::new(path)), expected);
+    }
+}
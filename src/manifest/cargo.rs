@@ -0,0 +1,212 @@
+use super::{Dependency, Error};
+use crate::Coordinates;
+use toml_edit::{Document, Item, TableLike, Value};
+
+static SECTIONS: &[&str] = &["dependencies", "dev-dependencies", "build-dependencies"];
+
+pub(super) fn dependencies(content: &str) -> Result<Vec<Dependency>, Error> {
+    let doc = Document::parse(content).map_err(Error::Toml)?;
+    let root = doc.as_table();
+
+    let targets = root
+        .get("target")
+        .and_then(Item::as_table_like)
+        .into_iter()
+        .flat_map(|targets| targets.iter())
+        .filter_map(|(_, target)| target.as_table_like());
+
+    let workspace = root
+        .get("workspace")
+        .and_then(|w| w.get("dependencies"))
+        .and_then(Item::as_table_like);
+
+    let dependencies = std::iter::once(root as &dyn TableLike)
+        .chain(targets)
+        .flat_map(|table| SECTIONS.iter().filter_map(move |s| table.get(s)))
+        .filter_map(Item::as_table_like)
+        .chain(workspace)
+        .flat_map(|deps| deps.iter())
+        .filter_map(|(name, dep)| dependency(content, name, dep))
+        .collect();
+
+    Ok(dependencies)
+}
+
+/// The dependencies in `[workspace.dependencies]` that members can inherit.
+pub(super) fn workspace_dependencies(content: &str) -> Result<Vec<Dependency>, Error> {
+    let doc = Document::parse(content).map_err(Error::Toml)?;
+    let dependencies = doc
+        .get("workspace")
+        .and_then(|w| w.get("dependencies"))
+        .and_then(Item::as_table_like)
+        .into_iter()
+        .flat_map(|deps| deps.iter())
+        .filter_map(|(name, dep)| dependency(content, name, dep))
+        .collect();
+
+    Ok(dependencies)
+}
+
+/// The `members` and `exclude` paths of a `[workspace]`.
+pub(super) type WorkspacePaths = (Vec<String>, Vec<String>);
+
+/// The paths of the `[workspace]`, or `None` if the manifest is not a workspace root.
+pub(super) fn workspace_members(content: &str) -> Result<Option<WorkspacePaths>, Error> {
+    let doc = Document::parse(content).map_err(Error::Toml)?;
+    let workspace = match doc.get("workspace").and_then(Item::as_table_like) {
+        Some(workspace) => workspace,
+        None => return Ok(None),
+    };
+    let paths = |key| {
+        workspace
+            .get(key)
+            .and_then(Item::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|path| path.as_str().map(String::from))
+            .collect::<Vec<_>>()
+    };
+    Ok(Some((paths("members"), paths("exclude"))))
+}
+
+/// The `package.name`, if the manifest is a package.
+pub(super) fn package_name(content: &str) -> Result<Option<String>, Error> {
+    let doc = Document::parse(content).map_err(Error::Toml)?;
+    Ok(doc
+        .get("package")
+        .and_then(|p| p.get("name"))
+        .and_then(Item::as_str)
+        .map(String::from))
+}
+
+/// The names of all dependencies that are inherited from the workspace with `workspace = true`.
+pub(super) fn inherited_dependencies(content: &str) -> Result<Vec<String>, Error> {
+    let doc = Document::parse(content).map_err(Error::Toml)?;
+    let root = doc.as_table();
+    let targets = root
+        .get("target")
+        .and_then(Item::as_table_like)
+        .into_iter()
+        .flat_map(|targets| targets.iter())
+        .filter_map(|(_, target)| target.as_table_like());
+
+    let inherited = std::iter::once(root as &dyn TableLike)
+        .chain(targets)
+        .flat_map(|table| SECTIONS.iter().filter_map(move |s| table.get(s)))
+        .filter_map(Item::as_table_like)
+        .flat_map(|deps| deps.iter())
+        .filter(|(_, dep)| {
+            dep.as_table_like()
+                .and_then(|dep| dep.get("workspace"))
+                .and_then(Item::as_bool)
+                == Some(true)
+        })
+        .map(|(name, _)| name.to_string())
+        .collect();
+
+    Ok(inherited)
+}
+
+fn dependency(content: &str, name: &str, dep: &Item) -> Option<Dependency> {
+    let (version, package) = match dep {
+        Item::Value(Value::String(version)) => (version, name),
+        dep => {
+            let dep = dep.as_table_like()?;
+            let version = match dep.get("version")? {
+                Item::Value(Value::String(version)) => version,
+                _ => return None,
+            };
+            let package = dep.get("package").and_then(Item::as_str).unwrap_or(name);
+            (version, package)
+        }
+    };
+
+    let span = string_span(content, version.span()?);
+    Dependency::new(
+        name,
+        Coordinates::Cargo(package.into()),
+        version.value(),
+        span,
+        false,
+    )
+}
+
+/// Shrinks the span of a string value to exclude the quotes.
+pub(super) fn string_span(content: &str, span: std::ops::Range<usize>) -> std::ops::Range<usize> {
+    let raw = &content[span.clone()];
+    let quotes = raw.len() - raw.trim_start_matches(['"', '\'']).len();
+    (span.start + quotes)..(span.end - quotes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cargo_dependencies() {
+        let content = r#"
+[package]
+name = "example"
+version = "0.1.0"
+
+[dependencies]
+serde = { version = "1.0.100", features = ["derive"] }
+tokio = "1"
+local = { path = "../local" }
+inherited = { workspace = true }
+renamed = { package = "real-name", version = '^0.4' }
+
+[dependencies.clap]
+version = "~4.0"
+default-features = false
+
+[dev-dependencies]
+test-case = "2.2.2"
+
+[target.'cfg(windows)'.build-dependencies]
+winres = "0.1"
+
+[workspace.dependencies]
+anyhow = "1.0.66"
+"#;
+        let deps = dependencies(content).unwrap();
+        let deps = deps
+            .iter()
+            .map(|d| {
+                (
+                    d.name.as_str(),
+                    d.coordinates.package_slug().into_owned(),
+                    d.raw_requirement(),
+                    &content[d.span.clone()],
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            deps,
+            vec![
+                ("serde", "serde".into(), "1.0.100", "1.0.100"),
+                ("tokio", "tokio".into(), "1", "1"),
+                ("renamed", "real-name".into(), "^0.4", "^0.4"),
+                ("clap", "clap".into(), "~4.0", "~4.0"),
+                ("test-case", "test-case".into(), "2.2.2", "2.2.2"),
+                ("winres", "winres".into(), "0.1", "0.1"),
+                ("anyhow", "anyhow".into(), "1.0.66", "1.0.66"),
+            ]
+        );
+    }
+
+    #[test]
+    fn inherited() {
+        let inherited = inherited_dependencies(
+            "[dependencies]\nserde = { workspace = true, features = [\"derive\"] }\ntokio = \"1\"\n\n[dev-dependencies]\nanyhow.workspace = true\n",
+        )
+        .unwrap();
+        assert_eq!(inherited, vec!["serde", "anyhow"]);
+    }
+
+    #[test]
+    fn invalid_toml() {
+        assert!(matches!(dependencies("[dependencies"), Err(Error::Toml(_))));
+    }
+}
@@ -0,0 +1,312 @@
+use super::{cargo::string_span, Dependency, Error};
+use crate::Coordinates;
+use std::{collections::HashMap, ops::Range};
+use toml_edit::{Document, Item, TableLike, Value};
+
+/// Reads `group:artifact:version` strings and `group: .., name: .., version: ..` maps of a `build.gradle(.kts)`.
+///
+/// Versions that use string interpolation are skipped.
+pub(super) fn dependencies(content: &str) -> Result<Vec<Dependency>, Error> {
+    let mut dependencies = Vec::new();
+    let mut map_entries = HashMap::new();
+    let mut map_line = 0;
+
+    for (value, span) in string_literals(content) {
+        if let Some(dep) = coordinate_dependency(&value, span.clone()) {
+            dependencies.extend(dep);
+            continue;
+        }
+
+        // map notation, all entries of one dependency are on the same line
+        let line_start = content[..span.start].rfind('\n').map_or(0, |i| i + 1);
+        if line_start != map_line {
+            map_entries.clear();
+            map_line = line_start;
+        }
+        let before = content[line_start..span.start - 1].trim_end();
+        let key = ["group", "name", "version"].iter().find(|key| {
+            before
+                .strip_suffix([':', '='])
+                .map(str::trim_end)
+                .is_some_and(|b| b.ends_with(*key))
+        });
+        if let Some(key) = key {
+            let _ = map_entries.insert(*key, (value, span));
+            if let (Some((group, _)), Some((name, _)), Some((version, span))) = (
+                map_entries.get("group"),
+                map_entries.get("name"),
+                map_entries.get("version"),
+            ) {
+                dependencies.extend(dependency(group, name, version, span.clone()));
+                map_entries.clear();
+            }
+        }
+    }
+
+    Ok(dependencies)
+}
+
+/// Reads the libraries of a `libs.versions.toml` version catalog, named by their alias.
+pub(super) fn catalog_dependencies(content: &str) -> Result<Vec<Dependency>, Error> {
+    let doc = Document::parse(content).map_err(Error::Toml)?;
+    let versions = doc.get("versions").and_then(Item::as_table_like);
+
+    let dependencies = doc
+        .get("libraries")
+        .and_then(Item::as_table_like)
+        .into_iter()
+        .flat_map(|libraries| libraries.iter())
+        .filter_map(|(alias, library)| catalog_dependency(content, versions, alias, library))
+        .collect();
+
+    Ok(dependencies)
+}
+
+fn catalog_dependency(
+    content: &str,
+    versions: Option<&dyn TableLike>,
+    alias: &str,
+    library: &Item,
+) -> Option<Dependency> {
+    if let Item::Value(Value::String(notation)) = library {
+        let span = string_span(content, notation.span()?);
+        let mut dep = coordinate_dependency(notation.value(), span)??;
+        dep.name = alias.into();
+        return Some(dep);
+    }
+
+    let library = library.as_table_like()?;
+    let (group, name) = match library.get("module").and_then(Item::as_str) {
+        Some(module) => module.split_once(':')?,
+        None => (
+            library.get("group")?.as_str()?,
+            library.get("name")?.as_str()?,
+        ),
+    };
+
+    let version = library.get("version")?;
+    let version = match version.as_table_like().and_then(|v| v.get("ref")) {
+        Some(reference) => versions?.get(reference.as_str()?)?,
+        None => version,
+    };
+    let (version, span) = catalog_version(content, version)?;
+
+    let mut dep = dependency(group, name, version, span)?;
+    dep.name = alias.into();
+    Some(dep)
+}
+
+/// A version is either a string or a rich version like `{ strictly = "1.2" }`.
+fn catalog_version<'a>(content: &str, version: &'a Item) -> Option<(&'a str, Range<usize>)> {
+    let version = match version.as_table_like() {
+        Some(rich) => ["strictly", "require", "prefer"]
+            .iter()
+            .find_map(|key| rich.get(key))?,
+        None => version,
+    };
+    match version {
+        Item::Value(Value::String(version)) => {
+            Some((version.value(), string_span(content, version.span()?)))
+        }
+        _ => None,
+    }
+}
+
+/// Parses `group:artifact:version[:classifier][@extension]`, where `span` is the span of the whole string.
+///
+/// Returns `None` if the string does not look like a dependency
+/// and `Some(None)` if it does, but the version cannot be used.
+fn coordinate_dependency(notation: &str, span: Range<usize>) -> Option<Option<Dependency>> {
+    let mut parts = notation.splitn(3, ':');
+    let group = parts.next()?;
+    let name = parts.next()?;
+    let rest = parts.next()?;
+    let is_identifier = |s: &str| {
+        !s.is_empty()
+            && s.chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+    };
+    if !is_identifier(group) || !is_identifier(name) {
+        return None;
+    }
+
+    let version = rest.split([':', '@']).next().unwrap_or_default();
+    let start = span.start + group.len() + name.len() + 2;
+    Some(dependency(
+        group,
+        name,
+        version,
+        start..start + version.len(),
+    ))
+}
+
+fn dependency(group: &str, name: &str, version: &str, span: Range<usize>) -> Option<Dependency> {
+    if version.contains('$') {
+        return None;
+    }
+    Dependency::new(
+        format!("{}:{}", group, name),
+        Coordinates::Maven {
+            group_id: group.into(),
+            artifact_id: name.into(),
+        },
+        version,
+        span,
+        true,
+    )
+}
+
+/// All single line string literals outside of comments, with the span of their content.
+fn string_literals(content: &str) -> Vec<(String, Range<usize>)> {
+    let bytes = content.as_bytes();
+    let mut literals = Vec::new();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b'/' if bytes.get(pos + 1) == Some(&b'/') => {
+                pos = content[pos..].find('\n').map_or(bytes.len(), |i| pos + i);
+            }
+            b'/' if bytes.get(pos + 1) == Some(&b'*') => {
+                pos = content[pos + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |i| pos + 2 + i + 2);
+            }
+            quote @ (b'"' | b'\'') => {
+                let start = pos + 1;
+                let mut end = start;
+                while end < bytes.len() && bytes[end] != quote && bytes[end] != b'\n' {
+                    end += if bytes[end] == b'\\' { 2 } else { 1 };
+                }
+                let end = end.min(bytes.len());
+                if bytes.get(end) == Some(&quote) {
+                    literals.push((content[start..end].to_string(), start..end));
+                }
+                pos = end + 1;
+            }
+            _ => pos += 1,
+        }
+    }
+    literals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary<'a>(content: &'a str, deps: &'a [Dependency]) -> Vec<(&'a str, String, &'a str)> {
+        deps.iter()
+            .map(|d| {
+                (
+                    d.name.as_str(),
+                    d.coordinates.package_slug().into_owned(),
+                    &content[d.span.clone()],
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn groovy_build_file() {
+        let content = r#"
+plugins {
+    id 'java'
+    id 'org.springframework.boot' version '2.7.5'
+}
+
+dependencies {
+    implementation 'com.google.guava:guava:31.1-jre'
+    // implementation 'commented:out:1.0'
+    testImplementation "org.junit.jupiter:junit-jupiter:5.9.1"
+    runtimeOnly group: 'org.neo4j', name: 'neo4j', version: '4.4.12'
+    implementation "org.neo4j.gds:proc:${gdsVersion}"
+    compileOnly 'org.projectlombok:lombok:1.18.24:sources@jar'
+    implementation project(':core')
+}
+"#;
+        let deps = dependencies(content).unwrap();
+        assert_eq!(
+            summary(content, &deps),
+            vec![
+                (
+                    "com.google.guava:guava",
+                    "com.google.guava:guava".into(),
+                    "31.1-jre"
+                ),
+                (
+                    "org.junit.jupiter:junit-jupiter",
+                    "org.junit.jupiter:junit-jupiter".into(),
+                    "5.9.1"
+                ),
+                ("org.neo4j:neo4j", "org.neo4j:neo4j".into(), "4.4.12"),
+                (
+                    "org.projectlombok:lombok",
+                    "org.projectlombok:lombok".into(),
+                    "1.18.24"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn kotlin_build_file() {
+        let content = r#"
+dependencies {
+    implementation("com.google.guava:guava:31.1-jre")
+    /* testImplementation("block:comment:1.0") */
+    implementation(group = "org.neo4j", name = "neo4j", version = "4.4.12")
+}
+"#;
+        let deps = dependencies(content).unwrap();
+        assert_eq!(
+            summary(content, &deps),
+            vec![
+                (
+                    "com.google.guava:guava",
+                    "com.google.guava:guava".into(),
+                    "31.1-jre"
+                ),
+                ("org.neo4j:neo4j", "org.neo4j:neo4j".into(), "4.4.12"),
+            ]
+        );
+    }
+
+    #[test]
+    fn version_catalog() {
+        let content = r#"
+[versions]
+neo4j = "4.4.12"
+junit = { strictly = "5.9.1" }
+
+[libraries]
+guava = "com.google.guava:guava:31.1-jre"
+neo4j-kernel = { module = "org.neo4j:neo4j-kernel", version.ref = "neo4j" }
+neo4j-bolt = { group = "org.neo4j", name = "neo4j-bolt", version = { ref = "neo4j" } }
+junit = { module = "org.junit.jupiter:junit-jupiter", version.ref = "junit" }
+lombok = { module = "org.projectlombok:lombok", version = "1.18.24" }
+bom = { module = "org.example:bom" }
+
+[plugins]
+boot = { id = "org.springframework.boot", version = "2.7.5" }
+"#;
+        let deps = catalog_dependencies(content).unwrap();
+        assert_eq!(
+            summary(content, &deps),
+            vec![
+                ("guava", "com.google.guava:guava".into(), "31.1-jre"),
+                ("neo4j-kernel", "org.neo4j:neo4j-kernel".into(), "4.4.12"),
+                ("neo4j-bolt", "org.neo4j:neo4j-bolt".into(), "4.4.12"),
+                ("junit", "org.junit.jupiter:junit-jupiter".into(), "5.9.1"),
+                ("lombok", "org.projectlombok:lombok".into(), "1.18.24"),
+            ]
+        );
+        assert_eq!(deps[1].span, deps[2].span);
+    }
+
+    #[test]
+    fn invalid_catalog() {
+        assert!(matches!(
+            catalog_dependencies("[libraries"),
+            Err(Error::Toml(_))
+        ));
+    }
+}
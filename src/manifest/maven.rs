@@ -0,0 +1,140 @@
+use super::{Dependency, Error};
+use crate::Coordinates;
+use roxmltree::{Document, Node};
+use std::ops::Range;
+
+pub(super) fn dependencies(content: &str) -> Result<Vec<Dependency>, Error> {
+    let doc = Document::parse(content).map_err(Error::Xml)?;
+    let project = doc.root_element();
+
+    let properties = child(project, "properties");
+    let dependency_lists = [
+        child(project, "dependencies"),
+        child(project, "dependencyManagement").and_then(|n| child(n, "dependencies")),
+    ];
+
+    let dependencies = dependency_lists
+        .iter()
+        .flatten()
+        .flat_map(|deps| deps.children().filter(|n| n.has_tag_name("dependency")))
+        .filter_map(|dep| {
+            let group_id = text(child(dep, "groupId")?)?.0;
+            let artifact_id = text(child(dep, "artifactId")?)?.0;
+            let (version, span) = resolve(properties, text(child(dep, "version")?)?)?;
+
+            Dependency::new(
+                format!("{}:{}", group_id, artifact_id),
+                Coordinates::Maven {
+                    group_id: group_id.into(),
+                    artifact_id: artifact_id.into(),
+                },
+                version,
+                span,
+                true,
+            )
+        })
+        .collect();
+
+    Ok(dependencies)
+}
+
+/// Follows a `${property}` reference to the definition of the property.
+fn resolve<'a>(
+    properties: Option<Node<'a, '_>>,
+    (version, span): (&'a str, Range<usize>),
+) -> Option<(&'a str, Range<usize>)> {
+    match version.strip_prefix("${").and_then(|v| v.strip_suffix('}')) {
+        Some(property) => text(child(properties?, property)?),
+        None => Some((version, span)),
+    }
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|c| c.has_tag_name(name))
+}
+
+/// The trimmed text of an element and its range in the document.
+fn text<'a>(node: Node<'a, '_>) -> Option<(&'a str, Range<usize>)> {
+    let text_node = node.first_child().filter(|c| c.is_text())?;
+    let raw = text_node.text()?;
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    let range = text_node.range();
+    let start = range.start + (raw.len() - raw.trim_start().len());
+    Some((trimmed, start..start + trimmed.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maven_dependencies() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0">
+  <modelVersion>4.0.0</modelVersion>
+  <properties>
+    <neo4j.version>4.2.6</neo4j.version>
+  </properties>
+  <dependencyManagement>
+    <dependencies>
+      <dependency>
+        <groupId>com.google.guava</groupId>
+        <artifactId>guava</artifactId>
+        <version>31.1-jre</version>
+      </dependency>
+    </dependencies>
+  </dependencyManagement>
+  <dependencies>
+    <dependency>
+      <groupId>org.neo4j</groupId>
+      <artifactId>neo4j</artifactId>
+      <version>${neo4j.version}</version>
+    </dependency>
+    <dependency>
+      <groupId>org.neo4j.gds</groupId>
+      <artifactId>proc</artifactId>
+      <version>
+        1.1.6
+      </version>
+    </dependency>
+    <dependency>
+      <groupId>com.google.guava</groupId>
+      <artifactId>guava</artifactId>
+    </dependency>
+    <dependency>
+      <groupId>org.example</groupId>
+      <artifactId>unknown-property</artifactId>
+      <version>${project.version}</version>
+    </dependency>
+  </dependencies>
+</project>"#;
+        let deps = dependencies(content).unwrap();
+        let deps = deps
+            .iter()
+            .map(|d| {
+                (
+                    d.coordinates.package_slug().into_owned(),
+                    d.raw_requirement(),
+                    &content[d.span.clone()],
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            deps,
+            vec![
+                ("org.neo4j:neo4j".into(), "4.2.6", "4.2.6"),
+                ("org.neo4j.gds:proc".into(), "1.1.6", "1.1.6"),
+                ("com.google.guava:guava".into(), "31.1-jre", "31.1-jre"),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_xml() {
+        assert!(matches!(dependencies("<project>"), Err(Error::Xml(_))));
+    }
+}
@@ -0,0 +1,329 @@
+use super::{Dependency, Error};
+use crate::Coordinates;
+use std::ops::Range;
+
+static SECTIONS: &[&str] = &[
+    "dependencies",
+    "devDependencies",
+    "peerDependencies",
+    "optionalDependencies",
+];
+
+pub(super) fn dependencies(content: &str) -> Result<Vec<Dependency>, Error> {
+    // validate with serde_json for proper error messages, the scanner below assumes valid JSON
+    let _ = serde_json::from_str::<serde_json::Value>(content).map_err(Error::Json)?;
+
+    let root = Scanner::new(content).value();
+    let dependencies = SECTIONS
+        .iter()
+        .filter_map(|section| root.get(section))
+        .flat_map(Json::entries)
+        .filter_map(|(name, req)| match req {
+            Json::String(req, span) => dependency(name, req, span.clone()),
+            _ => None,
+        })
+        .collect();
+
+    Ok(dependencies)
+}
+
+/// The `name` of the package, if it has one.
+pub(super) fn package_name(content: &str) -> Result<Option<String>, Error> {
+    let package = serde_json::from_str::<serde_json::Value>(content).map_err(Error::Json)?;
+    Ok(package
+        .get("name")
+        .and_then(serde_json::Value::as_str)
+        .map(String::from))
+}
+
+/// The `workspaces` globs, either as a list or as `{ "packages": [...] }` like yarn allows.
+pub(super) fn workspace_patterns(content: &str) -> Result<Vec<String>, Error> {
+    let package = serde_json::from_str::<serde_json::Value>(content).map_err(Error::Json)?;
+    let workspaces = package.get("workspaces");
+    let patterns = workspaces
+        .and_then(|w| w.get("packages"))
+        .or(workspaces)
+        .and_then(serde_json::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|pattern| pattern.as_str().map(String::from))
+        .collect();
+    Ok(patterns)
+}
+
+/// The globs in the `packages` list of a `pnpm-workspace.yaml`.
+///
+/// Only the block list style that pnpm documents is supported, not all of YAML.
+pub(super) fn pnpm_workspace_patterns(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .skip_while(|line| line.trim_end() != "packages:")
+        .skip(1)
+        .map(|line| line.split(" #").next().unwrap_or_default().trim_end())
+        .filter(|line| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .take_while(|line| line.starts_with([' ', '\t', '-']))
+        .filter_map(|line| line.trim_start().strip_prefix('-'))
+        .map(|pattern| pattern.trim().trim_matches(['\'', '"']).to_string())
+        .collect()
+}
+
+fn dependency(name: &str, req: &str, span: Range<usize>) -> Option<Dependency> {
+    // aliases, tarballs, git urls, github shorthands, workspace or local packages
+    if req.contains(':') || req.contains('/') {
+        return None;
+    }
+
+    let coordinates = match name.strip_prefix('@').and_then(|n| n.split_once('/')) {
+        Some((scope, package)) => Coordinates::Npm {
+            scope: Some(scope.into()),
+            package: package.into(),
+        },
+        None => Coordinates::Npm {
+            scope: None,
+            package: name.into(),
+        },
+    };
+
+    Dependency::new(name, coordinates, req, span, false)
+}
+
+/// A JSON value that remembers where its strings are in the source.
+#[derive(Debug)]
+pub(super) enum Json {
+    Object(Vec<(String, Json)>),
+    String(String, Range<usize>),
+    Other,
+}
+
+impl Json {
+    pub(super) fn get(&self, key: &str) -> Option<&Json> {
+        self.entries().find(|(k, _)| *k == key).map(|(_, v)| v)
+    }
+
+    pub(super) fn entries(&self) -> impl Iterator<Item = (&str, &Json)> {
+        let entries = match self {
+            Json::Object(entries) => &entries[..],
+            _ => &[],
+        };
+        entries.iter().map(|(k, v)| (k.as_str(), v))
+    }
+}
+
+/// A minimal JSON parser that keeps the byte ranges of string values.
+/// Only works on valid JSON.
+pub(super) struct Scanner<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub(super) fn new(input: &'a str) -> Self {
+        Self { input, pos: 0 }
+    }
+
+    pub(super) fn value(&mut self) -> Json {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'{') => {
+                self.pos += 1;
+                let mut entries = Vec::new();
+                loop {
+                    self.skip_whitespace();
+                    match self.peek() {
+                        Some(b'"') => {
+                            let (key, _) = self.string();
+                            self.skip_whitespace();
+                            self.pos += 1; // :
+                            let value = self.value();
+                            entries.push((key, value));
+                        }
+                        Some(b',') => self.pos += 1,
+                        Some(b'}') => {
+                            self.pos += 1;
+                            break;
+                        }
+                        _ => break,
+                    }
+                }
+                Json::Object(entries)
+            }
+            Some(b'[') => {
+                self.pos += 1;
+                loop {
+                    self.skip_whitespace();
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        Some(b']') => {
+                            self.pos += 1;
+                            break;
+                        }
+                        Some(_) => {
+                            let _ = self.value();
+                        }
+                        None => break,
+                    }
+                }
+                Json::Other
+            }
+            Some(b'"') => {
+                let (value, span) = self.string();
+                Json::String(value, span)
+            }
+            _ => {
+                while let Some(c) = self.peek() {
+                    if matches!(c, b',' | b'}' | b']') || c.is_ascii_whitespace() {
+                        break;
+                    }
+                    self.pos += 1;
+                }
+                Json::Other
+            }
+        }
+    }
+
+    fn string(&mut self) -> (String, Range<usize>) {
+        self.pos += 1;
+        let start = self.pos;
+        let mut value = String::new();
+        let mut chars = self.input[start..].char_indices();
+        while let Some((idx, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos = start + idx + 1;
+                    return (value, start..start + idx);
+                }
+                '\\' => match chars.next() {
+                    Some((_, 'n')) => value.push('\n'),
+                    Some((_, 't')) => value.push('\t'),
+                    Some((_, 'r')) => value.push('\r'),
+                    Some((_, 'b')) => value.push('\u{8}'),
+                    Some((_, 'f')) => value.push('\u{c}'),
+                    Some((_, 'u')) => {
+                        let hex = chars.by_ref().take(4).map(|(_, c)| c).collect::<String>();
+                        value.extend(u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32));
+                    }
+                    Some((_, c)) => value.push(c),
+                    None => break,
+                },
+                c => value.push(c),
+            }
+        }
+        self.pos = self.input.len();
+        (value, start..self.pos)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.pos).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn npm_dependencies() {
+        let content = r#"{
+  "name": "example",
+  "version": "1.0.0",
+  "scripts": { "test": "jest --coverage \"src/\"" },
+  "keywords": ["a", {"b": [1, 2.5e3, true, null]}],
+  "dependencies": {
+    "neo4j-driver": "^4.3.0",
+    "@types/neo4j": "~2.0",
+    "local": "file:../local",
+    "forked": "github:user/repo",
+    "aliased": "npm:other@^1",
+    "tagged": "latest"
+  },
+  "devDependencies": {
+    "jest": "29.x"
+  }
+}"#;
+        let deps = dependencies(content).unwrap();
+        let deps = deps
+            .iter()
+            .map(|d| {
+                (
+                    d.name.as_str(),
+                    d.coordinates.package_slug().into_owned(),
+                    d.raw_requirement(),
+                    &content[d.span.clone()],
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            deps,
+            vec![
+                ("neo4j-driver", "neo4j-driver".into(), "^4.3.0", "^4.3.0"),
+                ("@types/neo4j", "@types/neo4j".into(), "~2.0", "~2.0"),
+                ("jest", "jest".into(), "29.x", "29.x"),
+            ]
+        );
+    }
+
+    #[test]
+    fn workspaces() {
+        let npm = r#"{"name": "root", "workspaces": ["packages/*", "apps/web"]}"#;
+        assert_eq!(
+            workspace_patterns(npm).unwrap(),
+            vec!["packages/*", "apps/web"]
+        );
+        assert_eq!(package_name(npm).unwrap().as_deref(), Some("root"));
+
+        let yarn = r#"{"private": true, "workspaces": {"packages": ["packages/*"], "nohoist": ["**/react"]}}"#;
+        assert_eq!(workspace_patterns(yarn).unwrap(), vec!["packages/*"]);
+        assert_eq!(package_name(yarn).unwrap(), None);
+
+        assert!(workspace_patterns(r#"{"name": "single"}"#)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn pnpm_workspaces() {
+        let content = r#"
+# all packages
+packages:
+  # direct children
+  - 'packages/*'
+  - "components/**" # nested
+  - '!**/test/**'
+catalog:
+  react: ^18.2.0
+"#;
+        assert_eq!(
+            pnpm_workspace_patterns(content),
+            vec!["packages/*", "components/**", "!**/test/**"]
+        );
+    }
+
+    #[test]
+    fn string_escapes() {
+        let content = r#"{"a\"b": "é\n", "c": "d"}"#;
+        let json = Scanner::new(content).value();
+        match json.get("a\"b") {
+            Some(Json::String(value, span)) => {
+                assert_eq!(value, "é\n");
+                assert_eq!(&content[span.clone()], r#"é\n"#);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(matches!(json.get("c"), Some(Json::String(v, _)) if v == "d"));
+    }
+
+    #[test]
+    fn invalid_json() {
+        assert!(matches!(
+            dependencies(r#"{"dependencies": {"#),
+            Err(Error::Json(_))
+        ));
+    }
+}
@@ -0,0 +1,376 @@
+use super::{cargo, npm, Dependency, Error, Manifest, ManifestKind};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// A Cargo or npm/yarn/pnpm workspace made of a root manifest and the manifests of its members.
+///
+/// A manifest that is not a workspace root is a workspace with itself as the only member.
+#[derive(Debug, Clone)]
+pub struct Workspace {
+    root: Manifest,
+    members: Vec<Member>,
+}
+
+/// A member of a [`Workspace`] with its dependencies, including the ones it inherits from the root.
+#[derive(Debug, Clone)]
+pub struct Member {
+    pub name: String,
+    pub path: Option<PathBuf>,
+    pub dependencies: Vec<Dependency>,
+}
+
+/// A requirement on a package that is used by one or more members of a [`Workspace`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkspaceDependency<'a> {
+    pub dependency: &'a Dependency,
+    /// The names of the members that use the requirement, in the order of the workspace members.
+    pub members: Vec<&'a str>,
+}
+
+impl Workspace {
+    /// Reads the workspace with the root manifest at `path` and all of its members.
+    ///
+    /// If `path` is a directory, the first manifest file found in there is read.
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let root = Manifest::read(path)?;
+        let dir = root
+            .path()
+            .and_then(Path::parent)
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+
+        let mut members = match root.kind {
+            ManifestKind::Cargo => cargo_members(&root, &dir)?,
+            ManifestKind::Npm => npm_members(&root, &dir)?,
+            _ => vec![Member::of(&root, &dir, None, root.dependencies.clone())],
+        };
+
+        // members that depend on each other are not checked against the registry
+        let names = members.iter().map(|m| m.name.clone()).collect::<Vec<_>>();
+        for member in &mut members {
+            member
+                .dependencies
+                .retain(|dep| !names.contains(&dep.coordinates.package_slug().into_owned()));
+        }
+
+        Ok(Self { root, members })
+    }
+
+    /// The root manifest.
+    pub fn root(&self) -> &Manifest {
+        &self.root
+    }
+
+    /// All members, starting with the root if it is a member itself.
+    pub fn members(&self) -> &[Member] {
+        &self.members
+    }
+
+    /// All requirements of the members, deduplicated by package and requirement.
+    ///
+    /// The dependencies are in the order they first appear in the members.
+    pub fn dependencies(&self) -> Vec<WorkspaceDependency<'_>> {
+        let mut dependencies = Vec::<WorkspaceDependency<'_>>::new();
+        for member in &self.members {
+            for dep in &member.dependencies {
+                let existing = dependencies.iter_mut().find(|d| {
+                    d.dependency.coordinates == dep.coordinates && d.dependency.raw == dep.raw
+                });
+                match existing {
+                    Some(existing) => {
+                        if !existing.members.contains(&member.name.as_str()) {
+                            existing.members.push(&member.name);
+                        }
+                    }
+                    None => dependencies.push(WorkspaceDependency {
+                        dependency: dep,
+                        members: vec![&member.name],
+                    }),
+                }
+            }
+        }
+        dependencies
+    }
+}
+
+impl Member {
+    fn of(
+        manifest: &Manifest,
+        root_dir: &Path,
+        name: Option<String>,
+        dependencies: Vec<Dependency>,
+    ) -> Self {
+        let path = manifest.path().map(Path::to_path_buf);
+        let name = name.unwrap_or_else(|| {
+            path.as_deref()
+                .and_then(Path::parent)
+                .and_then(|dir| dir.strip_prefix(root_dir).ok())
+                .filter(|dir| !dir.as_os_str().is_empty())
+                .or(path.as_deref())
+                .map_or_else(|| String::from("root"), |p| p.display().to_string())
+        });
+        Self {
+            name,
+            path,
+            dependencies,
+        }
+    }
+}
+
+/// Expands the `members` globs of a `[workspace]` and resolves the dependencies with `workspace = true`.
+fn cargo_members(root: &Manifest, dir: &Path) -> Result<Vec<Member>, Error> {
+    let (patterns, exclude) = match cargo::workspace_members(&root.content)? {
+        Some(workspace) => workspace,
+        None => {
+            let name = cargo::package_name(&root.content)?;
+            return Ok(vec![Member::of(root, dir, name, root.dependencies.clone())]);
+        }
+    };
+
+    let inheritable = cargo::workspace_dependencies(&root.content)?;
+    // the root manifest also contains the inheritable dependencies, which are only used through inheritance
+    let resolve = |manifest: &Manifest, is_root: bool| -> Result<Vec<Dependency>, Error> {
+        let inherited = cargo::inherited_dependencies(&manifest.content)?;
+        let inherited = inherited
+            .iter()
+            .filter_map(|name| inheritable.iter().find(|dep| &dep.name == name));
+        Ok(manifest
+            .dependencies
+            .iter()
+            .filter(|dep| !is_root || !inheritable.iter().any(|d| d.span == dep.span))
+            .chain(inherited)
+            .cloned()
+            .collect())
+    };
+
+    let mut members = Vec::new();
+    if let Some(name) = cargo::package_name(&root.content)? {
+        members.push(Member::of(root, dir, Some(name), resolve(root, true)?));
+    }
+
+    for member_dir in member_dirs(dir, &patterns, &exclude, "Cargo.toml")? {
+        let manifest = Manifest::read(member_dir.join("Cargo.toml"))?;
+        let name = cargo::package_name(&manifest.content)?;
+        members.push(Member::of(&manifest, dir, name, resolve(&manifest, false)?));
+    }
+
+    Ok(members)
+}
+
+/// Expands the `workspaces` of the root `package.json`, or the `packages` of a `pnpm-workspace.yaml` next to it.
+///
+/// The root package is always a member, as monorepos usually keep their shared tooling there.
+fn npm_members(root: &Manifest, dir: &Path) -> Result<Vec<Member>, Error> {
+    let pnpm = dir.join("pnpm-workspace.yaml");
+    let patterns = if pnpm.is_file() {
+        match fs::read_to_string(&pnpm) {
+            Ok(content) => npm::pnpm_workspace_patterns(&content),
+            Err(e) => return Err(Error::Read(pnpm, e)),
+        }
+    } else {
+        npm::workspace_patterns(&root.content)?
+    };
+    let (exclude, patterns) = patterns
+        .into_iter()
+        .partition::<Vec<_>, _>(|pattern| pattern.starts_with('!'));
+    let exclude = exclude
+        .into_iter()
+        .map(|pattern| pattern[1..].to_string())
+        .collect::<Vec<_>>();
+
+    let name = npm::package_name(&root.content)?;
+    let mut members = vec![Member::of(root, dir, name, root.dependencies.clone())];
+    for member_dir in member_dirs(dir, &patterns, &exclude, "package.json")? {
+        let manifest = Manifest::read(member_dir.join("package.json"))?;
+        let name = npm::package_name(&manifest.content)?;
+        let dependencies = manifest.dependencies.clone();
+        members.push(Member::of(&manifest, dir, name, dependencies));
+    }
+
+    Ok(members)
+}
+
+/// The directories below `dir` that match one of the `patterns`, contain a `manifest` file,
+/// and are not in or below a directory that matches one of the `exclude` patterns.
+fn member_dirs(
+    dir: &Path,
+    patterns: &[String],
+    exclude: &[String],
+    manifest: &str,
+) -> Result<Vec<PathBuf>, Error> {
+    let expand = |patterns: &[String]| -> Result<Vec<PathBuf>, Error> {
+        let mut paths = Vec::new();
+        for pattern in patterns {
+            let pattern = dir.join(pattern.trim_start_matches("./"));
+            let matches = glob::glob(&pattern.to_string_lossy()).map_err(Error::Glob)?;
+            paths.extend(matches.filter_map(Result::ok));
+        }
+        Ok(paths)
+    };
+
+    let exclude = expand(exclude)?;
+    let mut member_dirs = expand(patterns)?
+        .into_iter()
+        .filter(|path| path.join(manifest).is_file())
+        .filter(|path| !exclude.iter().any(|e| path.starts_with(e)))
+        .collect::<Vec<_>>();
+    member_dirs.sort();
+    member_dirs.dedup();
+    Ok(member_dirs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::Fixture;
+
+    fn summary(workspace: &Workspace) -> Vec<(String, &str, Vec<&str>)> {
+        workspace
+            .dependencies()
+            .into_iter()
+            .map(|d| {
+                (
+                    d.dependency.coordinates.package_slug().into_owned(),
+                    d.dependency.raw_requirement(),
+                    d.members,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn cargo_workspace() {
+        let dir = Fixture::new();
+        dir.write(
+            "Cargo.toml",
+            r#"
+[workspace]
+members = ["crates/*"]
+exclude = ["crates/excluded"]
+
+[workspace.dependencies]
+serde = { version = "1.0.100", features = ["derive"] }
+anyhow = "1.0.60"
+"#,
+        );
+        dir.write(
+            "crates/app/Cargo.toml",
+            r#"
+[package]
+name = "app"
+
+[dependencies]
+serde = { workspace = true }
+tokio = "1.21"
+"#,
+        );
+        dir.write(
+            "crates/core/Cargo.toml",
+            r#"
+[package]
+name = "core"
+
+[dependencies]
+serde.workspace = true
+tokio = "1.21"
+
+[dev-dependencies]
+anyhow = { workspace = true }
+"#,
+        );
+        dir.write(
+            "crates/excluded/Cargo.toml",
+            "[package]\nname = \"excluded\"\n\n[dependencies]\nrand = \"0.8\"\n",
+        );
+        dir.write("crates/not-a-crate/README.md", "");
+
+        let workspace = Workspace::read(dir.path()).unwrap();
+        let members = workspace
+            .members()
+            .iter()
+            .map(|m| m.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(members, vec!["app", "core"]);
+        assert_eq!(
+            summary(&workspace),
+            vec![
+                ("tokio".into(), "1.21", vec!["app", "core"]),
+                ("serde".into(), "1.0.100", vec!["app", "core"]),
+                ("anyhow".into(), "1.0.60", vec!["core"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn single_package() {
+        let dir = Fixture::new();
+        dir.write(
+            "Cargo.toml",
+            "[package]\nname = \"single\"\n\n[dependencies]\nserde = \"1\"\n",
+        );
+
+        let workspace = Workspace::read(dir.join("Cargo.toml")).unwrap();
+        assert_eq!(
+            summary(&workspace),
+            vec![("serde".into(), "1", vec!["single"])]
+        );
+    }
+
+    #[test]
+    fn npm_workspace() {
+        let dir = Fixture::new();
+        dir.write("package.json",
+            r#"{"name": "monorepo", "workspaces": {"packages": ["packages/*"]}, "devDependencies": {"typescript": "^4.8.4"}}"#,
+        );
+        dir.write("packages/ui/package.json",
+            r#"{"name": "@acme/ui", "dependencies": {"react": "^17.0.2"}, "peerDependencies": {"react-dom": "^17.0.2"}}"#,
+        );
+        dir.write("packages/web/package.json",
+            r#"{"name": "web", "dependencies": {"react": "^17.0.2", "@acme/ui": "^1.0.0"}, "devDependencies": {"typescript": "^4.9.3"}}"#,
+        );
+
+        let workspace = Workspace::read(dir.path()).unwrap();
+        let members = workspace
+            .members()
+            .iter()
+            .map(|m| m.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(members, vec!["monorepo", "@acme/ui", "web"]);
+        assert_eq!(
+            summary(&workspace),
+            vec![
+                ("typescript".into(), "^4.8.4", vec!["monorepo"]),
+                ("react".into(), "^17.0.2", vec!["@acme/ui", "web"]),
+                ("react-dom".into(), "^17.0.2", vec!["@acme/ui"]),
+                ("typescript".into(), "^4.9.3", vec!["web"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn pnpm_workspace() {
+        let dir = Fixture::new();
+        dir.write("package.json", r#"{"private": true}"#);
+        dir.write(
+            "pnpm-workspace.yaml",
+            "packages:\n  - 'apps/*'\n  - '!apps/legacy'\n",
+        );
+        dir.write(
+            "apps/api/package.json",
+            r#"{"name": "api", "dependencies": {"express": "^4.18.2", "shared": "workspace:*"}}"#,
+        );
+        dir.write(
+            "apps/legacy/package.json",
+            r#"{"name": "legacy", "dependencies": {"express": "^3.0.0"}}"#,
+        );
+
+        let workspace = Workspace::read(dir.join("package.json")).unwrap();
+        assert_eq!(workspace.members().len(), 2);
+        assert_eq!(
+            summary(&workspace),
+            vec![("express".into(), "^4.18.2", vec!["api"])]
+        );
+    }
+}
@@ -1,35 +1,120 @@
-use crate::{Config, Coordinates, VersionCheck};
-use clap::{Arg, ArgAction, Command};
-use semver::VersionReq;
-use std::{fmt::Display, str::FromStr};
+use crate::{
+    advisories::Advisories,
+    config::ConfigFile,
+    deps::{Deps, DepsFormat},
+    diff::Diff,
+    update::{Bump, Update},
+    watch::{parse_interval, Watch},
+    Config,
+};
+use clap::{error::ErrorKind, Arg, ArgAction, Command};
+use color_eyre::eyre::Result;
+use console::style;
+use latest_version::{
+    parse_coordinates, parse_coordinates_list, Coordinates, Lockfile, Manifest, PreReleaseChannel,
+    VersionCheck, VersionFilter, VersionScheme, Workspace, SEMVER_RANGE_SYNTAX,
+};
+use regex::Regex;
+use reqwest::Url;
+use semver::Version;
+use std::{
+    io::{self, Read},
+    sync::Arc,
+};
 
 #[derive(Debug)]
 #[cfg_attr(test, derive(Default))]
 pub(crate) struct Opts {
     version_checks: Vec<VersionCheck>,
     include_pre_releases: bool,
+    pre_release_channel: Option<PreReleaseChannel>,
+    version_filter: Option<Arc<VersionFilter>>,
+    include_yanked: bool,
+    advisories: Option<Advisories>,
+    licenses: bool,
+    deps: Option<Deps>,
+    suggest: bool,
+    diagnostics: bool,
+    natural_fallback: bool,
+    registries: Vec<(String, Url)>,
+    watch: Option<Watch>,
+    manifests: Vec<Manifest>,
+    update: Option<Update>,
+    lockfiles: Vec<Lockfile>,
+    workspaces: Vec<Workspace>,
+    diff: Option<Diff>,
 }
 
 impl Opts {
-    pub(crate) fn new() -> Self {
-        Self::from_matches(Self::app().get_matches())
+    pub(crate) fn new() -> Result<Self> {
+        // parse first, so that `--help` and `--version` work even with a broken config file
+        let matches = Self::app().get_matches();
+        let config = ConfigFile::load()?;
+        Ok(Self::from_matches(matches, config, &mut io::stdin()).unwrap_or_else(|e| e.exit()))
     }
 
     #[cfg(test)]
     fn of(args: &[&str]) -> Result<Self, clap::Error> {
+        Self::of_with_config(args, ConfigFile::default())
+    }
+
+    #[cfg(test)]
+    fn of_with_config(args: &[&str], config: ConfigFile) -> Result<Self, clap::Error> {
+        Self::of_with_stdin(args, config, "")
+    }
+
+    #[cfg(test)]
+    fn of_with_stdin(args: &[&str], config: ConfigFile, stdin: &str) -> Result<Self, clap::Error> {
         let args = args.to_vec();
         let matches = Self::app()
             .no_binary_name(true)
             .try_get_matches_from(args)?;
-        Ok(Self::from_matches(matches))
+        Self::from_matches(matches, config, &mut stdin.as_bytes())
     }
 
     pub(crate) fn config(&self) -> Config {
         Config {
             include_pre_releases: self.include_pre_releases,
+            pre_release_channel: self.pre_release_channel,
+            version_filter: self.version_filter.clone(),
+            include_yanked: self.include_yanked,
+            advisories: self.advisories,
+            licenses: self.licenses,
+            deps: self.deps,
+            suggest: self.suggest,
+            diagnostics: self.diagnostics,
+            natural_fallback: self.natural_fallback,
         }
     }
 
+    pub(crate) fn watch(&self) -> Option<Watch> {
+        self.watch.clone()
+    }
+
+    pub(crate) fn update(&self) -> Option<Update> {
+        self.update
+    }
+
+    pub(crate) fn manifests(&self) -> &[Manifest] {
+        &self.manifests
+    }
+
+    pub(crate) fn diff(&self) -> Option<Diff> {
+        self.diff.clone()
+    }
+
+    pub(crate) fn lockfiles(&self) -> &[Lockfile] {
+        &self.lockfiles
+    }
+
+    pub(crate) fn workspaces(&self) -> &[Workspace] {
+        &self.workspaces
+    }
+
+    pub(crate) fn registries(&self) -> &[(String, Url)] {
+        &self.registries
+    }
+
     pub(crate) fn into_version_checks(self) -> Vec<VersionCheck> {
         self.version_checks
     }
@@ -43,14 +128,170 @@ impl Opts {
                 Arg::new("include-pre-releases")
                     .action(ArgAction::SetTrue)
                     .help("Also consider pre releases")
+                    .long_help("Also consider pre releases. Can also be enabled with `include_pre_releases = true` in the config file. A single requirement can override this by ending with `?pre` to include or `?stable` to exclude pre releases, e.g. `cargo:tokio:^1:^2?pre`.")
                     .short('i')
                     .long("include-pre-releases"),
+            ).arg(
+                Arg::new("pre")
+                    .value_name("CHANNEL")
+                    .value_parser(parse_pre_release_channel)
+                    .help("Also consider pre releases, but only of CHANNEL or a more stable channel")
+                    .long_help("Also consider pre releases, but only of CHANNEL or a more stable channel. The channels from the least to the most stable one are `nightly` (also `snapshot`, `dev`, `canary`), `alpha`, `beta` (also `preview`), `milestone` (`M1`), and `rc` (also `cr`). For example, `--pre rc` considers `-rc.1`, but not `-alpha02` or `-SNAPSHOT`. Pre releases with unknown tags are skipped.")
+                    .long("pre"),
+            ).arg(
+                Arg::new("include-version")
+                    .value_name("REGEX")
+                    .action(ArgAction::Append)
+                    .value_parser(parse_version_pattern)
+                    .allow_hyphen_values(true)
+                    .help("Only consider versions that match REGEX")
+                    .long_help("Only consider versions that match REGEX, e.g. `-jre$` for the jre variant of guava. If specified multiple times, versions that match any of the patterns are considered. For a single check, add an `include=REGEX` segment instead, e.g. `com.google.guava:guava:^31:include=-jre$`.")
+                    .long("include-version"),
+            ).arg(
+                Arg::new("exclude-version")
+                    .value_name("REGEX")
+                    .action(ArgAction::Append)
+                    .value_parser(parse_version_pattern)
+                    .allow_hyphen_values(true)
+                    .help("Do not consider versions that match REGEX")
+                    .long_help("Do not consider versions that match REGEX, e.g. `nightly` or `-android$`. Can be specified multiple times. For a single check, add an `exclude=REGEX` segment instead, e.g. `com.google.guava:guava:^31:exclude=-android$`.")
+                    .long("exclude-version"),
+            ).arg(
+                Arg::new("include-yanked")
+                    .action(ArgAction::SetTrue)
+                    .help("Also consider yanked, deprecated, or retracted versions")
+                    .long_help("Also consider versions that were yanked (crates.io), deprecated (npm), or retracted (go). Can also be enabled with `include_yanked = true` in the config file.")
+                    .long("include-yanked"),
+            ).arg(
+                Arg::new("advisories")
+                    .value_name("MODE")
+                    .value_parser(["show", "exclude"])
+                    .num_args(0..=1)
+                    .require_equals(true)
+                    .default_missing_value("show")
+                    .help("Show known security advisories of the current and the latest versions")
+                    .long_help("Show known security advisories (ID and severity) of the current and the latest versions. The current version is the one declared in a manifest or locked in a lockfile. With `--advisories=exclude`, versions with known advisories are also skipped when looking for the latest versions.")
+                    .long("advisories"),
+            ).arg(
+                Arg::new("licenses")
+                    .action(ArgAction::SetTrue)
+                    .help("Show the licenses of the latest versions")
+                    .long_help("Show the licenses of the latest versions. If the license differs from the one of the current version, i.e. the version declared in a manifest or locked in a lockfile, the change is highlighted.")
+                    .long("licenses"),
+            ).arg(
+                Arg::new("deps")
+                    .value_name("DEPTH")
+                    .value_parser(["direct", "transitive"])
+                    .num_args(0..=1)
+                    .require_equals(true)
+                    .default_missing_value("direct")
+                    .help("Show the dependencies of the latest versions")
+                    .long_help("Show the dependencies of the latest versions, as resolved by deps.dev. With `--deps=transitive`, the dependencies of the dependencies are shown as well, packages that already appeared are marked with `(*)` and not expanded again.")
+                    .long("deps"),
+            ).arg(
+                Arg::new("deps-format")
+                    .value_name("FORMAT")
+                    .value_parser(["tree", "json"])
+                    .default_value("tree")
+                    .requires("deps")
+                    .help("How --deps prints the dependencies")
+                    .long_help("How --deps prints the dependencies. `json` prints one JSON object per line for every latest version.")
+                    .long("deps-format"),
+            ).arg(
+                Arg::new("suggest")
+                    .action(ArgAction::SetTrue)
+                    .help("Print ready-to-paste dependency declarations for the latest versions")
+                    .long_help("Print ready-to-paste dependency declarations for the latest versions in the syntax of the ecosystem: a Cargo.toml line, a package.json entry, a Maven <dependency> and a Gradle coordinate, or a `go get` command.")
+                    .long("suggest"),
+            ).arg(
+                Arg::new("diagnostics")
+                    .action(ArgAction::SetTrue)
+                    .help("Report versions that could not be parsed")
+                    .long_help("Report the versions of every package that could not be parsed as a version, e.g. `r09` or `latest`. Those versions never match a requirement, so this helps to tell whether \"No version\" means that there are no versions or none that could be read. The report is printed to stderr.")
+                    .long("diagnostics"),
+            ).arg(
+                Arg::new("natural-fallback")
+                    .action(ArgAction::SetTrue)
+                    .help("Order versions that could not be parsed naturally, if no other version is found")
+                    .long_help("Order versions that could not be parsed naturally, comparing runs of digits as numbers and anything else as text, so that `r10` comes after `r9`. The latest of those versions is only reported for a requirement that allows any version, or as the latest version overall, and only if no version that could be parsed is found.")
+                    .long("natural-fallback"),
+            ).arg(
+                Arg::new("stdin")
+                    .action(ArgAction::SetTrue)
+                    .conflicts_with("update")
+                    .help("Read additional checks from stdin, one per line")
+                    .long_help("Read additional checks from stdin, one per line. Blank lines and comments starting with `#` are skipped. Same as passing `-` as the last check.")
+                    .long("stdin"),
+            ).arg(
+                Arg::new("watch")
+                    .value_name("INTERVAL")
+                    .value_parser(parse_interval)
+                    .help("Keep running and re-check every INTERVAL, reporting only changes")
+                    .long_help("Keep running and re-check every INTERVAL (e.g. `30s`, `15m`, `1h`, `1d`). The first results are always reported, afterwards only versions that changed are printed.")
+                    .long("watch"),
+            ).arg(
+                Arg::new("on-change")
+                    .value_name("COMMAND")
+                    .requires("watch")
+                    .help("Run COMMAND for every changed version in watch mode")
+                    .long_help("Run COMMAND with `sh -c` (`cmd /C` on Windows) for every version that changed in watch mode. The change is passed in the environment variables LATEST_VERSION_SYSTEM, LATEST_VERSION_PACKAGE, LATEST_VERSION_REQUIREMENT, and LATEST_VERSION_VERSION (empty if there is no longer any matching version).")
+                    .long("on-change"),
+            ).arg(
+                Arg::new("manifest")
+                    .value_name("FILE")
+                    .action(ArgAction::Append)
+                    .value_parser(parse_manifest)
+                    .help("Check all dependencies of a Cargo.toml, package.json, pom.xml, build.gradle(.kts), or Gradle version catalog")
+                    .long_help("Check all dependencies of a Cargo.toml, package.json, pom.xml, build.gradle(.kts), or Gradle version catalog (libs.versions.toml). If FILE is a directory, the first of those files in the directory is used. Can be specified multiple times.")
+                    .long("manifest"),
+            ).arg(
+                Arg::new("update")
+                    .action(ArgAction::SetTrue)
+                    .requires("manifest")
+                    .conflicts_with("watch")
+                    .help("Rewrite the requirements in the manifests to the latest versions")
+                    .long_help("Rewrite the requirements in the manifests to the latest versions. Only the requirements are changed, the formatting and comments of the files are kept. Requirements made of multiple ranges are not updated.")
+                    .long("update"),
+            ).arg(
+                Arg::new("dry-run")
+                    .action(ArgAction::SetTrue)
+                    .requires("update")
+                    .help("Show the changes of --update without writing them")
+                    .long("dry-run"),
+            ).arg(
+                Arg::new("bump")
+                    .value_name("BUMP")
+                    .value_parser(["compatible", "latest"])
+                    .default_value("compatible")
+                    .requires("update")
+                    .help("Which versions --update bumps to")
+                    .long_help("Which versions --update bumps to. `compatible` only uses versions that still match the current requirement, `latest` uses the latest version, even if it is a new major version.")
+                    .long("bump"),
+            ).arg(
+                Arg::new("lockfile")
+                    .value_name("FILE")
+                    .action(ArgAction::Append)
+                    .value_parser(parse_lockfile)
+                    .conflicts_with_all(["watch", "update"])
+                    .help("Compare the locked versions of a Cargo.lock, package-lock.json, or go.sum to the latest versions")
+                    .long_help("Compare the locked versions of a Cargo.lock, package-lock.json, or go.sum to the latest version matching the requirement and to the latest version overall. Transitive dependencies are included. The requirements of direct dependencies are taken from the Cargo.toml or package.json next to the lockfile, for all other packages, versions compatible to the locked one are matched. If FILE is a directory, the first of those files in the directory is used. Can be specified multiple times.")
+                    .long("lockfile"),
+            ).arg(
+                Arg::new("workspace")
+                    .value_name("FILE")
+                    .action(ArgAction::Append)
+                    .value_parser(parse_workspace)
+                    .conflicts_with_all(["watch", "update"])
+                    .help("Check all members of a Cargo or npm/yarn/pnpm workspace and report which members use outdated requirements")
+                    .long_help("Check all members of a Cargo or npm/yarn/pnpm workspace, starting from the root Cargo.toml or package.json. Cargo members are found with the `members` and `exclude` of the `[workspace]`, dependencies with `workspace = true` are resolved to the `[workspace.dependencies]`. npm and yarn members are found with the `workspaces` of the package.json, pnpm members with the `packages` of the pnpm-workspace.yaml next to it. Every requirement is checked only once, outdated requirements are reported together with the members that use them. Dependencies between members are skipped. If FILE is a directory, the manifest in the directory is used. Can be specified multiple times.")
+                    .long("workspace"),
             ).arg(
                 Arg::new("version-checks")
                     .action(ArgAction::Append)
+                    .conflicts_with("update")
                     .num_args(1..)
                     .allow_negative_numbers(true)
-                    .value_parser(parse_coordinates)
+                    .value_parser(parse_check_arg)
                     .help("The maven coordinates to check for. Can be specified multiple times")
                     .long_help(r#"
 The maven coordinates to check for. Can be specified multiple times.
@@ -60,212 +301,299 @@ The versions are treated as requirement qualifiers.
 Every matching version will be collected into the same bucket per requirement.
 The latest version per bucket is then shown.
 The value for a requirement follow the semver range specification from https://www.npmjs.com/package/semver#advanced-range-syntax
+A bare version has the meaning of the system: `1.2` is `^1.2` for cargo, `1.2.x` for npm, exactly `1.2.0` for maven, and `>=1.2, <2` for go.
+Maven version ranges like `[1.0,2.0)` are supported as well.
+Instead of a version, a segment can be `scheme=calver` for calendar versions like `2023.10.1` or `20231017`,
+or `within={duration}` like `1y` or `30d` to only consider versions released within that time.
+A `:` inside a segment, e.g. in an `include={regex}` or `exclude={regex}` segment, is escaped as `\:`.
+
+An argument of the form `@{group}` runs all checks of that group from the `[groups]` of the config file.
+An argument of `-` reads the checks from stdin, one per line.
 
 Multiple checks will be run concurrently and may be printed out of order."#)
-                    )
+            ).subcommand(
+                Command::new("diff")
+                    .about("List all versions of a package that were published between two versions")
+                    .long_about("List all versions of a package that were published after FROM up to and including TO, together with their release date. Yanked, deprecated, or retracted versions are marked, as are versions that are not compatible to the previous one.")
+                    .arg(
+                        Arg::new("package")
+                            .required(true)
+                            .value_parser(parse_diff_package)
+                            .help("The package, e.g. `cargo:serde`"),
+                    ).arg(
+                        Arg::new("from")
+                            .required(true)
+                            .value_parser(parse_diff_version)
+                            .help("The version to start after"),
+                    ).arg(
+                        Arg::new("to")
+                            .required(true)
+                            .value_parser(parse_diff_version)
+                            .help("The last version to list"),
+                    ),
+            )
     }
 
-    fn from_matches(mut matches: clap::ArgMatches) -> Self {
-        Opts {
-            version_checks: matches
-                .remove_many("version-checks")
-                .map_or_else(Vec::new, |v| v.collect()),
-            include_pre_releases: matches.get_flag("include-pre-releases"),
+    fn from_matches(
+        mut matches: clap::ArgMatches,
+        config: ConfigFile,
+        stdin: &mut dyn Read,
+    ) -> Result<Self, clap::Error> {
+        // clap only knows conflicts between arguments, the checks would be dropped silently for a diff
+        if matches.subcommand_name() == Some("diff")
+            && (matches.contains_id("version-checks") || matches.get_flag("stdin"))
+        {
+            return Err(Self::app().error(
+                ErrorKind::ArgumentConflict,
+                "Version checks cannot be used with the diff subcommand",
+            ));
         }
-    }
-}
 
-#[non_exhaustive]
-#[derive(Debug)]
-pub(crate) enum Error {
-    Missing(&'static str, String),
-    InvalidRange(String, semver::Error),
-}
+        let mut version_checks = Vec::new();
+        let mut stdin = Some(stdin);
+        let read_stdin = matches.get_flag("stdin").then(|| CheckArg::Stdin);
+        for arg in matches
+            .remove_many::<CheckArg>("version-checks")
+            .into_iter()
+            .flatten()
+            .chain(read_stdin)
+        {
+            match arg {
+                CheckArg::Check(check) => version_checks.push(check),
+                CheckArg::Stdin => {
+                    let stdin = match stdin.take() {
+                        Some(stdin) => stdin,
+                        None => continue,
+                    };
+                    let mut input = String::new();
+                    let _ = stdin.read_to_string(&mut input)?;
+                    let checks = parse_coordinates_list(&input).map_err(|e| {
+                        Self::app().error(
+                            ErrorKind::ValueValidation,
+                            format!("Invalid check from stdin: {}", styled_error(&e)),
+                        )
+                    })?;
+                    version_checks.extend(checks);
+                }
+                CheckArg::Group(group) => {
+                    let checks = config.groups.get(&group).ok_or_else(|| {
+                        Self::app()
+                            .error(ErrorKind::InvalidValue, format!("Unknown group @{}", group))
+                    })?;
+                    for check in checks {
+                        let check = parse_coordinates(check).map_err(|e| {
+                            Self::app().error(
+                                ErrorKind::ValueValidation,
+                                format!("Invalid check in group @{}: {}", group, styled_error(&e)),
+                            )
+                        })?;
+                        version_checks.push(check);
+                    }
+                }
+            }
+        }
 
-impl FromStr for VersionCheck {
-    type Err = Error;
+        let manifests = matches
+            .remove_many::<Manifest>("manifest")
+            .map_or_else(Vec::new, |m| m.collect());
+        version_checks.extend(
+            manifests
+                .iter()
+                .flat_map(Manifest::dependencies)
+                .map(|dep| VersionCheck {
+                    coordinates: dep.coordinates.clone(),
+                    versions: vec![dep.requirement.clone()],
+                    current: dep.current_version(),
+                    filter: VersionFilter::new(),
+                    scheme: VersionScheme::default(),
+                    released_within: None,
+                    name: Some(dep.name.clone())
+                        .filter(|name| *name != dep.coordinates.package_slug()),
+                }),
+        );
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        parse_coordinates(s)
-    }
-}
+        let lockfiles = matches
+            .remove_many::<Lockfile>("lockfile")
+            .map_or_else(Vec::new, |l| l.collect());
 
-fn parse_coordinates(input: &str) -> Result<VersionCheck, Error> {
-    let mut segments = input.split(':').map(str::trim).peekable();
+        let workspaces = matches
+            .remove_many::<Workspace>("workspace")
+            .map_or_else(Vec::new, |w| w.collect());
 
-    let coordinates = match segments.next() {
-        Some("maven") => {
-            let group_id = match segments.next() {
-                Some(group_id) if !group_id.is_empty() => group_id,
-                _ => return Err(Error::Missing("group_id", input.into())),
-            };
+        let diff = match matches.remove_subcommand() {
+            Some((name, mut diff)) if name == "diff" => Some(Diff {
+                coordinates: diff
+                    .remove_one::<Coordinates>("package")
+                    .expect("required argument"),
+                from: diff.remove_one("from").expect("required argument"),
+                to: diff.remove_one("to").expect("required argument"),
+            }),
+            _ => None,
+        };
 
-            let artifact_id = match segments.next() {
-                Some(artifact_id) if !artifact_id.is_empty() => artifact_id,
-                _ => return Err(Error::Missing("artifact_id", input.into())),
-            };
+        let version_filter = matches
+            .remove_many::<Regex>("include-version")
+            .into_iter()
+            .flatten()
+            .fold(VersionFilter::new(), VersionFilter::include);
+        let version_filter = matches
+            .remove_many::<Regex>("exclude-version")
+            .into_iter()
+            .flatten()
+            .fold(version_filter, VersionFilter::exclude);
+        // every task gets a clone of the config, the filter with its regexes is shared instead of copied
+        let version_filter = (!version_filter.is_empty()).then(|| Arc::new(version_filter));
 
-            Coordinates::Maven {
-                group_id: group_id.into(),
-                artifact_id: artifact_id.into(),
-            }
-        }
-        Some("cargo") => {
-            let package = match segments.next() {
-                Some(package) if !package.is_empty() => package,
-                Some(_) => match segments.next() {
-                    Some(package) if !package.is_empty() => package,
-                    _ => return Err(Error::Missing("package", input.into())),
-                },
-                _ => return Err(Error::Missing("package", input.into())),
-            };
+        let update = matches.get_flag("update").then(|| Update {
+            bump: match matches.get_one::<String>("bump").map(String::as_str) {
+                Some("latest") => Bump::Latest,
+                _ => Bump::Compatible,
+            },
+            dry_run: matches.get_flag("dry-run"),
+        });
 
-            Coordinates::Cargo(package.into())
-        }
-        Some("npm") => match segments.next() {
-            Some(scope_or_package) if !scope_or_package.is_empty() => {
-                if scope_or_package.starts_with('@') {
-                    let scope = scope_or_package.trim_start_matches('@');
-                    if scope.is_empty() {
-                        return Err(Error::Missing("scope", input.into()));
-                    }
-                    if let Some((scope, package)) = scope.split_once('/') {
-                        if package.is_empty() {
-                            return Err(Error::Missing("package", input.into()));
-                        }
-                        Coordinates::Npm {
-                            scope: Some(scope.into()),
-                            package: package.into(),
-                        }
-                    } else {
-                        let package = match segments.next() {
-                            Some(package) if !package.is_empty() => package,
-                            _ => return Err(Error::Missing("package", input.into())),
-                        };
-                        Coordinates::Npm {
-                            scope: Some(scope.into()),
-                            package: package.into(),
-                        }
-                    }
-                } else if let Some((scope, package)) = scope_or_package.split_once('/') {
-                    if scope.is_empty() {
-                        return Err(Error::Missing("scope", input.into()));
-                    }
-                    if package.is_empty() {
-                        return Err(Error::Missing("package", input.into()));
-                    }
-                    Coordinates::Npm {
-                        scope: Some(scope.into()),
-                        package: package.into(),
-                    }
-                } else {
-                    match segments.peek() {
-                        Some(package)
-                            if !package.is_empty() && VersionReq::parse(package).is_err() =>
-                        {
-                            let coords = Coordinates::Npm {
-                                scope: Some(scope_or_package.into()),
-                                package: (*package).into(),
-                            };
-                            let _ = segments.next();
-                            coords
-                        }
-                        _ => Coordinates::Npm {
-                            scope: None,
-                            package: scope_or_package.into(),
-                        },
-                    }
-                }
-            }
-            _ => return Err(Error::Missing("package", input.into())),
-        },
-        Some("go") => match segments.next() {
-            Some(gomod) if !gomod.is_empty() && gomod.contains('/') => {
-                Coordinates::AnyGo(gomod.into())
-            }
-            Some(user) if !user.is_empty() => match segments.next() {
-                Some(gomod) if !gomod.is_empty() => Coordinates::Go {
-                    user: user.into(),
-                    module: gomod.into(),
+        let mut registries = config
+            .registries
+            .into_iter()
+            .map(|(system, registry)| (system, registry.url))
+            .collect::<Vec<_>>();
+        registries.sort();
+
+        Ok(Opts {
+            version_checks,
+            include_pre_releases: matches.get_flag("include-pre-releases")
+                || config.include_pre_releases
+                || matches.contains_id("pre"),
+            pre_release_channel: matches.get_one("pre").copied(),
+            version_filter,
+            include_yanked: matches.get_flag("include-yanked") || config.include_yanked,
+            advisories: matches
+                .get_one::<String>("advisories")
+                .map(|mode| match mode.as_str() {
+                    "exclude" => Advisories::Exclude,
+                    _ => Advisories::Show,
+                }),
+            licenses: matches.get_flag("licenses"),
+            suggest: matches.get_flag("suggest"),
+            diagnostics: matches.get_flag("diagnostics"),
+            natural_fallback: matches.get_flag("natural-fallback"),
+            deps: matches.get_one::<String>("deps").map(|depth| Deps {
+                transitive: depth == "transitive",
+                format: match matches.get_one::<String>("deps-format").map(String::as_str) {
+                    Some("json") => DepsFormat::Json,
+                    _ => DepsFormat::Tree,
                 },
-                _ => return Err(Error::Missing("module", input.into())),
-            },
-            _ => return Err(Error::Missing("user", input.into())),
-        },
-        Some("github.com") => {
-            let user = match segments.next() {
-                Some(user) if !user.is_empty() => user,
-                _ => return Err(Error::Missing("user", input.into())),
-            };
-
-            let module = match segments.next() {
-                Some(module) if !module.is_empty() => module,
-                _ => return Err(Error::Missing("module", input.into())),
-            };
-
-            Coordinates::Go {
-                user: user.into(),
-                module: module.into(),
-            }
-        }
-        Some(group_id) if !group_id.is_empty() => {
-            let artifact_id = match segments.next() {
-                Some(artifact_id) if !artifact_id.is_empty() => artifact_id,
-                _ => return Err(Error::Missing("artifact_id", input.into())),
-            };
+            }),
+            registries,
+            watch: matches.remove_one("watch").map(|interval| Watch {
+                interval,
+                hook: matches.remove_one("on-change"),
+            }),
+            manifests,
+            update,
+            lockfiles,
+            workspaces,
+            diff,
+        })
+    }
+}
 
-            Coordinates::Maven {
-                group_id: group_id.into(),
-                artifact_id: artifact_id.into(),
-            }
-        }
-        _ => return Err(Error::Missing("group_id", input.into())),
-    };
+#[derive(Debug, Clone)]
+enum CheckArg {
+    Check(VersionCheck),
+    Group(String),
+    Stdin,
+}
+
+fn parse_manifest(path: &str) -> Result<Manifest, latest_version::ManifestError> {
+    Manifest::read(path)
+}
+
+fn parse_lockfile(path: &str) -> Result<Lockfile, latest_version::LockfileError> {
+    Lockfile::read(path)
+}
 
-    let versions = segments.map(parse_version).collect::<Result<Vec<_>, _>>()?;
+fn parse_workspace(path: &str) -> Result<Workspace, latest_version::ManifestError> {
+    Workspace::read(path)
+}
 
-    Ok(VersionCheck {
-        coordinates,
-        versions,
+fn parse_pre_release_channel(input: &str) -> Result<PreReleaseChannel, String> {
+    PreReleaseChannel::from_tag(input).ok_or_else(|| {
+        format!(
+            "Unknown channel {}, expected one of nightly, alpha, beta, milestone, rc",
+            input
+        )
     })
 }
 
-fn parse_version(version: &str) -> Result<VersionReq, Error> {
-    if version.is_empty() {
-        Ok(VersionReq::STAR)
-    } else {
-        VersionReq::parse(version).map_err(|e| Error::InvalidRange(version.into(), e))
-    }
+fn parse_version_pattern(input: &str) -> Result<Regex, regex::Error> {
+    Regex::new(input)
+}
+
+fn parse_diff_package(input: &str) -> Result<Coordinates, String> {
+    parse_coordinates(input)
+        .map(|check| check.coordinates)
+        .map_err(|e| styled_error(&e))
+}
+
+fn parse_diff_version(input: &str) -> Result<Version, String> {
+    lenient_semver::parse(input).map_err(|e| e.to_string())
 }
 
-impl Display for Error {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Error::Missing(what, input) => write!(f, "Missing {} in {}", what, input),
-            Error::InvalidRange(input, _) => write!(
-                f,
-                "Could not parse {} into a semantic version range. Please provide a valid range according to {}",
-                console::style(input).red().bold(),
-                console::style("https://www.npmjs.com/package/semver#advanced-range-syntax").cyan().underlined(),
-            ),
+fn parse_check_arg(input: &str) -> Result<CheckArg, String> {
+    if input.trim() == "-" {
+        return Ok(CheckArg::Stdin);
+    }
+    match input.trim().strip_prefix('@') {
+        Some(group) if !group.is_empty() && !group.contains(':') => {
+            Ok(CheckArg::Group(group.into()))
         }
+        _ => parse_coordinates(input)
+            .map(CheckArg::Check)
+            .map_err(|e| styled_error(&e)),
     }
 }
 
-impl std::error::Error for Error {
-    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        if let Error::InvalidRange(_, src) = self {
-            Some(src)
-        } else {
-            None
-        }
+/// The message of a check error with the offending input highlighted, the library only reports plain text.
+///
+/// The wording is the one of the library, but the input is styled where it appears in the message,
+/// not wherever the same text happens to show up first.
+fn styled_error(error: &latest_version::Error) -> String {
+    use latest_version::Error;
+    let highlight = |input: &str| style(input.to_string()).red().bold();
+    match error {
+        Error::Missing(what, input) => format!("Missing {} in {}", what, highlight(input)),
+        Error::InvalidRange(input, _) => format!(
+            "Could not parse {} into a semantic version range. Please provide a valid range according to {}",
+            highlight(input),
+            style(SEMVER_RANGE_SYNTAX).cyan().underlined(),
+        ),
+        Error::InvalidPattern(input, e) => format!(
+            "Could not parse {} into a regular expression: {}",
+            highlight(input),
+            e
+        ),
+        Error::InvalidScheme(input) => format!(
+            "Unknown version scheme {}, expected one of semver or calver",
+            highlight(input)
+        ),
+        Error::InvalidDuration(input) => format!(
+            "Could not parse {} into a duration like 1y, 6mo, 2w, or 30d",
+            highlight(input)
+        ),
+        Error::Line(line, error) => format!("{} on line {}", styled_error(error), line),
+        error => error.to_string(),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use clap::error::{ContextKind, ContextValue, ErrorKind};
+    use crate::fixture::Fixture;
+    use clap::error::{ContextKind, ContextValue};
+    use latest_version::Coordinates;
+    use latest_version::Requirement;
+    use std::time::Duration;
     use test_case::test_case;
 
     #[test]
@@ -314,33 +642,11 @@ mod tests {
                 assert_eq!(group_id, expected_group_id);
                 assert_eq!(artifact_id, expected_artifact_id);
             }
-            wrong => assert!(false, "{:?}", wrong),
+            wrong => panic!("{:?}", wrong),
         }
         assert_eq!(checks.next(), None);
     }
 
-    #[test_case(":foo", Error::Missing("group_id", ":foo".into()); "empty_group_id_1")]
-    #[test_case(":foo:", Error::Missing("group_id", ":foo:".into()); "empty_group_id_2")]
-    #[test_case("", Error::Missing("group_id", "".into()); "empty_group_id_3")]
-    #[test_case(":", Error::Missing("group_id", ":".into()); "empty_group_id_4")]
-    #[test_case("::", Error::Missing("group_id", "::".into()); "empty_group_id_5")]
-    #[test_case("  ", Error::Missing("group_id", "  ".into()); "empty_group_id_6")]
-    #[test_case("  :", Error::Missing("group_id", "  :".into()); "empty_group_id_7")]
-    #[test_case("foo:", Error::Missing("artifact_id", "foo:".into()); "empty_artifact_1")]
-    #[test_case("foo::", Error::Missing("artifact_id", "foo::".into()); "empty_artifact_2")]
-    #[test_case("foo: ", Error::Missing("artifact_id", "foo: ".into()); "empty_artifact_3")]
-    #[test_case("foo: :", Error::Missing("artifact_id", "foo: :".into()); "empty_artifact_4")]
-    #[test_case("foo", Error::Missing("artifact_id", "foo".into()); "missing_artifact")]
-    fn test_invalid_coords(arg: &str, expected: Error) {
-        match (parse_coordinates(arg).unwrap_err(), expected) {
-            (Error::Missing(lhs1, lhs2), Error::Missing(rhs1, rhs2)) => {
-                assert_eq!(lhs1, rhs1);
-                assert_eq!(lhs2, rhs2);
-            }
-            (lhs, rhs) => panic!("Different errors: left == {} right == {}", lhs, rhs),
-        }
-    }
-
     #[test_case(":foo"; "empty_group_id_1")]
     #[test_case(":foo:"; "empty_group_id_2")]
     #[test_case(":"; "empty_group_id_4")]
@@ -368,12 +674,12 @@ mod tests {
         assert_eq!(context, expected);
     }
 
-    #[test_case("foo:bar:1", vec!["1"]; "version 1")]
-    #[test_case("foo:bar:0", vec!["0"]; "version 0")]
+    #[test_case("foo:bar:1", vec!["=1.0.0"]; "version 1")]
+    #[test_case("foo:bar:0", vec!["=0.0.0"]; "version 0")]
     #[test_case("foo:bar:*", vec!["*"]; "any version")]
     #[test_case("foo:bar:", vec!["*"]; "empty version")]
     #[test_case("foo:bar", vec![]; "no version")]
-    #[test_case("foo:bar:1.0", vec!["1.0"]; "version 1.0")]
+    #[test_case("foo:bar:1.0", vec!["=1.0.0"]; "version 1.0")]
     #[test_case("foo:bar:1.x", vec!["1.x"]; "version 1.x")]
     #[test_case("foo:bar:1.*", vec!["1.*"]; "version 1.*")]
     #[test_case("foo:bar:=1.2.3", vec!["=1.2.3"]; "exact version")]
@@ -381,13 +687,22 @@ mod tests {
     #[test_case("foo:bar:>1.2.3", vec![">1.2.3"]; "gt version")]
     #[test_case("foo:bar:<=1.2.3", vec!["<=1.2.3"]; "lte version")]
     #[test_case("foo:bar:>=1.2.3", vec![">=1.2.3"]; "gte version")]
-    #[test_case("foo:bar:1.2.3, 2", vec!["1.2.3, 2"]; "multi range with space")]
-    #[test_case("foo:bar:1.2.3,2", vec!["1.2.3,2"]; "multi range with comma")]
-    #[test_case("foo:bar:1.2.3:2", vec!["1.2.3", "2"]; "multiple ranges")]
+    #[test_case("foo:bar:1.2.3, 2", vec!["=1.2.3, =2.0.0"]; "multi range with space")]
+    #[test_case("foo:bar:1.2.3,2", vec!["=1.2.3, =2.0.0"]; "multi range with comma")]
+    #[test_case("foo:bar:1.2.3:2", vec!["=1.2.3", "=2.0.0"]; "multiple ranges")]
+    #[test_case("foo:bar:1.2.3 2", vec!["=1.2.3, =2.0.0"]; "multi range with space separator")]
+    #[test_case("foo:bar:>=1.2 <2", vec![">=1.2, <2"]; "npm comparators")]
+    #[test_case("foo:bar:1.2 - 2.3", vec![">=1.2, <=2.3"]; "hyphen range")]
+    #[test_case("foo:bar:^1 || ^2:3.x", vec!["^1 || ^2", "3.x"]; "alternatives")]
+    #[test_case("foo:bar:[1.0,2.0)", vec![">=1.0.0, <2.0.0"]; "maven range")]
+    #[test_case("cargo:serde:1.0", vec!["^1.0"]; "cargo bare version")]
+    #[test_case("cargo:tokio:^1:^2?pre", vec!["^1", "^2?pre"]; "pre release override")]
+    #[test_case("npm:react:17", vec!["=17"]; "npm bare version")]
+    #[test_case("com.google.guava:guava:^31@jre:^31@android", vec!["^31@jre", "^31@android"]; "variants")]
     fn test_version_arg_range(arg: &str, ranges: Vec<&str>) {
         let ranges = ranges
             .into_iter()
-            .map(VersionReq::parse)
+            .map(Requirement::parse)
             .collect::<Result<Vec<_>, _>>()
             .unwrap();
         let opts = Opts::of(&[arg]).unwrap();
@@ -409,7 +724,7 @@ mod tests {
     #[test_case("foo:bar:*42"; "mixed star and version")]
     #[test_case("foo:bar:1.3.3.7"; "4 segments")]
     #[test_case("foo:bar:1:foo"; "second version fails")]
-    #[test_case("foo:bar:1.2.3 2"; "multi range with space separator")]
+    #[test_case("foo:bar:^1 || qux"; "invalid alternative")]
     fn test_version_arg_invalid_range(arg: &str) {
         let err = Opts::of(&[arg]).unwrap_err();
         assert_eq!(err.kind(), ErrorKind::ValueValidation);
@@ -426,18 +741,401 @@ mod tests {
         assert_eq!(context, expected);
     }
 
+    #[test]
+    fn test_group_arg() {
+        let config = ConfigFile {
+            groups: vec![(
+                String::from("backend"),
+                vec![String::from("cargo:tokio:^1"), String::from("cargo:serde")],
+            )]
+            .into_iter()
+            .collect(),
+            ..ConfigFile::default()
+        };
+        let opts = Opts::of_with_config(&["foo:bar", "@backend"], config).unwrap();
+        let checks = opts
+            .version_checks
+            .into_iter()
+            .map(|c| c.coordinates.package_slug().into_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(checks, ["foo:bar", "tokio", "serde"]);
+    }
+
+    #[test]
+    fn test_unknown_group_arg() {
+        let err = Opts::of(&["@backend"]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidValue);
+    }
+
+    #[test]
+    fn test_invalid_check_in_group() {
+        let config = ConfigFile {
+            groups: vec![(String::from("broken"), vec![String::from("foo:bar:qux")])]
+                .into_iter()
+                .collect(),
+            ..ConfigFile::default()
+        };
+        let err = Opts::of_with_config(&["@broken"], config).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+    }
+
+    #[test_case(&["foo:bar", "-", "baz:qux"], &["foo:bar", "tokio", "serde", "baz:qux"]; "dash keeps order")]
+    #[test_case(&["foo:bar", "--stdin"], &["foo:bar", "tokio", "serde"]; "flag appends")]
+    #[test_case(&["--stdin"], &["tokio", "serde"]; "flag only")]
+    #[test_case(&["-", "--stdin", "-"], &["tokio", "serde"]; "read once")]
+    fn test_stdin_arg(args: &[&str], expected: &[&str]) {
+        let stdin = "# deps\ncargo:tokio:^1\n\ncargo:serde\n";
+        let opts = Opts::of_with_stdin(args, ConfigFile::default(), stdin).unwrap();
+        let checks = opts
+            .version_checks
+            .into_iter()
+            .map(|c| c.coordinates.package_slug().into_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(checks, expected);
+    }
+
+    #[test]
+    fn test_invalid_stdin() {
+        let err =
+            Opts::of_with_stdin(&["-"], ConfigFile::default(), "foo:bar\nfoo:\n").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+    }
+
+    #[test]
+    fn test_default_watch() {
+        let opts = Opts::of(&["foo:bar"]).unwrap();
+        assert_eq!(opts.watch(), None);
+    }
+
+    #[test]
+    fn test_watch() {
+        let opts = Opts::of(&["foo:bar", "--watch", "5m", "--on-change", "echo new"]).unwrap();
+        assert_eq!(
+            opts.watch(),
+            Some(Watch {
+                interval: Duration::from_secs(300),
+                hook: Some(String::from("echo new")),
+            })
+        );
+    }
+
+    #[test]
+    fn test_hook_requires_watch() {
+        let err = Opts::of(&["foo:bar", "--on-change", "echo new"]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn test_default_update() {
+        let opts = Opts::of(&["foo:bar"]).unwrap();
+        assert_eq!(opts.update(), None);
+    }
+
+    #[test_case(&["--update"], Bump::Compatible, false; "update")]
+    #[test_case(&["--update", "--dry-run"], Bump::Compatible, true; "dry run")]
+    #[test_case(&["--update", "--bump", "latest"], Bump::Latest, false; "latest")]
+    #[test_case(&["--update", "--bump", "compatible", "--dry-run"], Bump::Compatible, true; "compatible")]
+    fn test_update(args: &[&str], bump: Bump, dry_run: bool) {
+        let dir = cargo_toml_fixture();
+        let args = [&["--manifest", "DIR"], args].concat();
+        let opts = Opts::of(&with_dir(&args, dir.path())).unwrap();
+        assert_eq!(opts.update(), Some(Update { bump, dry_run }));
+        assert_eq!(opts.manifests().len(), 1);
+    }
+
+    #[test_case(&["--update"], ErrorKind::MissingRequiredArgument; "update without manifest")]
+    #[test_case(&["--manifest", "DIR", "--dry-run"], ErrorKind::MissingRequiredArgument; "dry run without update")]
+    #[test_case(&["--manifest", "DIR", "--update", "--watch", "1h"], ErrorKind::ArgumentConflict; "update with watch")]
+    #[test_case(&["--manifest", "DIR", "--update", "--bump", "major"], ErrorKind::InvalidValue; "unknown bump")]
+    #[test_case(&["--manifest", "Cargo.lock"], ErrorKind::ValueValidation; "unknown manifest")]
+    #[test_case(&["--manifest", "DIR", "--bump", "latest"], ErrorKind::MissingRequiredArgument; "bump without update")]
+    #[test_case(&["--manifest", "DIR", "--update", "foo:bar"], ErrorKind::ArgumentConflict; "update with checks")]
+    #[test_case(&["--manifest", "DIR", "--update", "--stdin"], ErrorKind::ArgumentConflict; "update with stdin")]
+    fn test_invalid_update(args: &[&str], kind: ErrorKind) {
+        let dir = cargo_toml_fixture();
+        let err = Opts::of(&with_dir(args, dir.path())).unwrap_err();
+        assert_eq!(err.kind(), kind);
+    }
+
+    #[test_case(&[], None; "default")]
+    #[test_case(&["--advisories"], Some(Advisories::Show); "flag")]
+    #[test_case(&["--advisories=show"], Some(Advisories::Show); "show")]
+    #[test_case(&["--advisories=exclude"], Some(Advisories::Exclude); "exclude")]
+    fn test_advisories(args: &[&str], expected: Option<Advisories>) {
+        let args = [args, &["foo:bar"]].concat();
+        let opts = Opts::of(&args).unwrap();
+        assert_eq!(opts.config().advisories, expected);
+    }
+
+    #[test_case(&[], None; "default")]
+    #[test_case(&["--deps"], Some(Deps { transitive: false, format: DepsFormat::Tree }); "flag")]
+    #[test_case(&["--deps=transitive"], Some(Deps { transitive: true, format: DepsFormat::Tree }); "transitive")]
+    #[test_case(&["--deps", "--deps-format", "json"], Some(Deps { transitive: false, format: DepsFormat::Json }); "json")]
+    fn test_deps(args: &[&str], expected: Option<Deps>) {
+        let args = [args, &["foo:bar"]].concat();
+        let opts = Opts::of(&args).unwrap();
+        assert_eq!(opts.config().deps, expected);
+    }
+
+    #[test]
+    fn test_deps_format_requires_deps() {
+        let err = Opts::of(&["--deps-format", "json", "foo:bar"]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn test_diff() {
+        let opts = Opts::of(&["-i", "diff", "cargo:serde", "1.0.100", "1.0.150"]).unwrap();
+        assert_eq!(
+            opts.diff(),
+            Some(Diff {
+                coordinates: Coordinates::Cargo("serde".into()),
+                from: Version::new(1, 0, 100),
+                to: Version::new(1, 0, 150),
+            })
+        );
+        assert!(opts.config().include_pre_releases);
+    }
+
+    #[test_case(&["diff", "cargo:serde", "1.0.100"], ErrorKind::MissingRequiredArgument; "missing to")]
+    #[test_case(&["diff", "cargo:serde", "latest", "1.0.150"], ErrorKind::ValueValidation; "invalid version")]
+    #[test_case(&["diff", "foo", "1.0.0", "2.0.0"], ErrorKind::ValueValidation; "invalid package")]
+    #[test_case(&["foo:bar", "-i", "diff", "cargo:serde", "1.0.0", "2.0.0"], ErrorKind::ArgumentConflict; "diff with checks")]
+    #[test_case(&["--stdin", "diff", "cargo:serde", "1.0.0", "2.0.0"], ErrorKind::ArgumentConflict; "diff with stdin")]
+    fn test_invalid_diff(args: &[&str], kind: ErrorKind) {
+        let err = Opts::of(args).unwrap_err();
+        assert_eq!(err.kind(), kind);
+    }
+
+    #[test_case("foo:bar:qux"; "invalid range")]
+    #[test_case("foo:bar:a"; "input that is also part of the message")]
+    #[test_case("foo:"; "missing")]
+    #[test_case("foo:bar:include=("; "invalid pattern")]
+    #[test_case("foo:bar:scheme=date"; "invalid scheme")]
+    #[test_case("foo:bar:within=1x"; "invalid duration")]
+    fn test_styled_error(input: &str) {
+        console::set_colors_enabled(false);
+        let error = parse_coordinates(input).unwrap_err();
+        assert!(!error.to_string().contains('\u{1b}'));
+        assert_eq!(styled_error(&error), error.to_string());
+
+        let error = parse_coordinates_list(&format!("\n{}", input)).unwrap_err();
+        assert_eq!(styled_error(&error), error.to_string());
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let config = Opts::of(&["foo:bar"]).unwrap().config();
+        assert!(!config.diagnostics);
+        assert!(!config.natural_fallback);
+
+        let config = Opts::of(&["--diagnostics", "--natural-fallback", "foo:bar"])
+            .unwrap()
+            .config();
+        assert!(config.diagnostics);
+        assert!(config.natural_fallback);
+    }
+
+    #[test]
+    fn test_include_yanked() {
+        assert!(!Opts::of(&["foo:bar"]).unwrap().config().include_yanked);
+        assert!(
+            Opts::of(&["--include-yanked", "foo:bar"])
+                .unwrap()
+                .config()
+                .include_yanked
+        );
+        let config = ConfigFile {
+            include_yanked: true,
+            ..ConfigFile::default()
+        };
+        assert!(
+            Opts::of_with_config(&["foo:bar"], config)
+                .unwrap()
+                .config()
+                .include_yanked
+        );
+    }
+
+    /// A Cargo.lock with one direct and one transitive dependency, next to its Cargo.toml.
+    fn cargo_lock_fixture() -> Fixture {
+        let fixture = cargo_toml_fixture();
+        fixture.write(
+            "Cargo.lock",
+            "version = 3\n\n[[package]]\nname = \"example\"\nversion = \"0.1.0\"\n\n[[package]]\nname = \"semver\"\nversion = \"1.0.14\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n\n[[package]]\nname = \"serde\"\nversion = \"1.0.100\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n",
+        );
+        fixture
+    }
+
+    #[test]
+    fn test_lockfile() {
+        let dir = cargo_lock_fixture();
+        let lockfile = dir.join("Cargo.lock");
+        let opts = Opts::of(&["--lockfile", lockfile.to_str().unwrap()]).unwrap();
+        assert_eq!(opts.lockfiles().len(), 1);
+        let packages = opts.lockfiles()[0].packages();
+        assert!(packages
+            .iter()
+            .any(|p| p.direct && p.coordinates.package_slug() == "semver"));
+        assert!(packages
+            .iter()
+            .any(|p| !p.direct && p.coordinates.package_slug() == "serde"));
+    }
+
+    #[test_case(&["--lockfile", "DIR", "--watch", "1h"], ErrorKind::ArgumentConflict; "lockfile with watch")]
+    #[test_case(&["--lockfile", "DIR", "--manifest", "DIR", "--update"], ErrorKind::ArgumentConflict; "lockfile with update")]
+    #[test_case(&["--lockfile", "Cargo.toml"], ErrorKind::ValueValidation; "unknown lockfile")]
+    fn test_invalid_lockfile(args: &[&str], kind: ErrorKind) {
+        let dir = cargo_lock_fixture();
+        let err = Opts::of(&with_dir(args, dir.path())).unwrap_err();
+        assert_eq!(err.kind(), kind);
+    }
+
+    /// A Cargo.toml with a single dependency.
+    fn cargo_toml_fixture() -> Fixture {
+        let fixture = Fixture::new();
+        fixture.write(
+            "Cargo.toml",
+            "[package]\nname = \"example\"\n\n[dependencies]\nsemver = \"1\"\n",
+        );
+        fixture
+    }
+
+    /// Replaces every `DIR` in `args` with `dir`.
+    fn with_dir<'a>(args: &[&'a str], dir: &'a std::path::Path) -> Vec<&'a str> {
+        let dir = dir.to_str().unwrap();
+        args.iter()
+            .map(|arg| if *arg == "DIR" { dir } else { arg })
+            .collect()
+    }
+
+    #[test]
+    fn test_workspace() {
+        let dir = cargo_toml_fixture();
+        let opts = Opts::of(&with_dir(&["--workspace", "DIR"], dir.path())).unwrap();
+        assert_eq!(opts.workspaces().len(), 1);
+        let members = opts.workspaces()[0].members();
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].name, "example");
+        assert!(opts.version_checks.is_empty());
+    }
+
+    #[test_case(&["--workspace", "DIR", "--watch", "1h"], ErrorKind::ArgumentConflict; "workspace with watch")]
+    #[test_case(&["--workspace", "DIR", "--manifest", "DIR", "--update"], ErrorKind::ArgumentConflict; "workspace with update")]
+    #[test_case(&["--workspace", "Cargo.lock"], ErrorKind::ValueValidation; "unknown manifest")]
+    fn test_invalid_workspace(args: &[&str], kind: ErrorKind) {
+        let dir = cargo_toml_fixture();
+        let err = Opts::of(&with_dir(args, dir.path())).unwrap_err();
+        assert_eq!(err.kind(), kind);
+    }
+
+    #[test]
+    fn test_manifest_checks() {
+        let dir = cargo_toml_fixture();
+        let opts = Opts::of(&with_dir(&["foo:bar", "--manifest", "DIR"], dir.path())).unwrap();
+        let deps = opts.manifests()[0].dependencies().len();
+        assert!(deps > 0);
+        assert_eq!(opts.version_checks.len(), deps + 1);
+        assert_eq!(opts.version_checks[0].coordinates.package_slug(), "foo:bar");
+    }
+
+    #[test]
+    fn test_catalog_alias() {
+        let dir = Fixture::new();
+        dir.write(
+            "libs.versions.toml",
+            "[libraries]\nguava = \"com.google.guava:guava:31.1-jre\"\n",
+        );
+        let catalog = dir.join("libs.versions.toml");
+        let opts = Opts::of(&["--manifest", catalog.to_str().unwrap()]).unwrap();
+        assert_eq!(opts.version_checks.len(), 1);
+        assert_eq!(
+            opts.version_checks[0].coordinates.package_slug(),
+            "com.google.guava:guava"
+        );
+        assert_eq!(opts.version_checks[0].name.as_deref(), Some("guava"));
+
+        let dir = cargo_toml_fixture();
+        let opts = Opts::of(&with_dir(&["--manifest", "DIR"], dir.path())).unwrap();
+        assert_eq!(opts.version_checks[0].name, None);
+    }
+
+    #[test]
+    fn test_pre_release_from_config() {
+        let config = ConfigFile {
+            include_pre_releases: true,
+            ..ConfigFile::default()
+        };
+        let opts = Opts::of_with_config(&["foo:bar"], config).unwrap();
+        assert!(opts.config().include_pre_releases);
+    }
+
     #[test]
     fn test_default_pre_release_flag() {
         let opts = Opts::default();
-        assert_eq!(opts.include_pre_releases, false);
-        assert_eq!(opts.config().include_pre_releases, false);
+        assert!(!opts.include_pre_releases);
+        assert!(!opts.config().include_pre_releases);
     }
 
     #[test_case("-i"; "short flag")]
     #[test_case("--include-pre-releases"; "long flag")]
     fn test_pre_release_flag(flag: &str) {
         let opts = Opts::of(&[flag]).unwrap();
-        assert_eq!(opts.include_pre_releases, true);
-        assert_eq!(opts.config().include_pre_releases, true);
+        assert!(opts.include_pre_releases);
+        assert!(opts.config().include_pre_releases);
+    }
+
+    #[test_case("rc", PreReleaseChannel::Rc; "rc")]
+    #[test_case("SNAPSHOT", PreReleaseChannel::Nightly; "snapshot")]
+    #[test_case("beta", PreReleaseChannel::Beta; "beta")]
+    fn test_pre_release_channel(channel: &str, expected: PreReleaseChannel) {
+        let opts = Opts::of(&["--pre", channel, "foo:bar"]).unwrap();
+        assert!(opts.config().include_pre_releases);
+        assert_eq!(opts.config().pre_release_channel, Some(expected));
+    }
+
+    #[test]
+    fn test_unknown_pre_release_channel() {
+        let err = Opts::of(&["--pre", "gamma", "foo:bar"]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+    }
+
+    #[test]
+    fn test_version_filter() {
+        let opts = Opts::of(&[
+            "--include-version",
+            "-jre$",
+            "--exclude-version",
+            "^2",
+            "com.google.guava:guava",
+        ])
+        .unwrap();
+        let filter = opts.config().version_filter.unwrap();
+        assert!(filter.matches("31.1-jre"));
+        assert!(!filter.matches("31.1-android"));
+        assert!(!filter.matches("21.0-jre"));
+        assert!(opts.version_checks[0].filter.is_empty());
+    }
+
+    #[test]
+    fn test_calver_check() {
+        let opts = Opts::of(&["npm:foo:2023:scheme=calver:within=1y"]).unwrap();
+        let check = &opts.version_checks[0];
+        assert_eq!(check.scheme, VersionScheme::CalVer);
+        assert_eq!(check.versions, vec![Requirement::parse("=2023").unwrap()]);
+        assert!(check.released_within.is_some());
+    }
+
+    #[test]
+    fn test_no_version_filter() {
+        let opts = Opts::of(&["foo:bar"]).unwrap();
+        assert!(opts.config().version_filter.is_none());
+    }
+
+    #[test_case(&["--include-version", "(", "foo:bar"]; "invalid include")]
+    #[test_case(&["foo:bar:exclude=("]; "invalid exclude in check")]
+    fn test_invalid_version_filter(args: &[&str]) {
+        let err = Opts::of(args).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
     }
 }
@@ -0,0 +1,490 @@
+use crate::{scheme::normalize_calver, Coordinates};
+use semver::{Version, VersionReq};
+use std::{borrow::Cow, fmt::Display, str::FromStr};
+
+/// A requirement on the version of a package, made of one or more alternative ranges.
+///
+/// Next to the syntax of cargo, the range syntax of npm is supported:
+/// alternatives separated by `||`, hyphen ranges like `1.2 - 2.3`,
+/// and comparators separated by spaces like `>=1.2 <2`.
+/// See [`Coordinates::parse_requirement`] for the syntax and meaning of requirements in other ecosystems.
+///
+/// A requirement can end with `?pre` or `?stable` to include or exclude pre releases,
+/// regardless of what is configured for all requirements.
+/// Before that, `@variant` selects a variant line of releases like the `31.1-jre` or `31.1-android` versions of guava.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Requirement {
+    /// Empty if any version is allowed.
+    ranges: Vec<VersionReq>,
+    pre_releases: Option<bool>,
+    variant: Option<String>,
+}
+
+impl Requirement {
+    /// A requirement that allows any version.
+    pub const STAR: Self = Self {
+        ranges: Vec::new(),
+        pre_releases: None,
+        variant: None,
+    };
+
+    /// Parses a requirement in cargo or npm syntax, where a bare version like `1.2` means `^1.2`.
+    pub fn parse(input: &str) -> Result<Self, semver::Error> {
+        Self::parse_with(input, Ecosystem::Cargo)
+    }
+
+    /// Parses a requirement on calendar versions, where dates are written like the versions,
+    /// e.g. `2023.*`, `>=20230601`, or `23.10`, and a bare date like `2023` means any version from that year.
+    pub fn parse_calver(input: &str) -> Result<Self, semver::Error> {
+        Self::parse_with(input, Ecosystem::CalVer)
+    }
+
+    fn parse_with(input: &str, ecosystem: Ecosystem) -> Result<Self, semver::Error> {
+        let input = input.trim();
+        let (input, pre_releases) = match input.rsplit_once('?') {
+            Some((input, "pre")) => (input.trim(), Some(true)),
+            Some((input, "stable")) => (input.trim(), Some(false)),
+            _ => (input, None),
+        };
+        let (input, variant) = match input.rsplit_once('@') {
+            Some((input, variant))
+                if !variant.is_empty() && variant.chars().all(|c| c.is_ascii_alphanumeric()) =>
+            {
+                (input.trim(), Some(variant.to_string()))
+            }
+            _ => (input, None),
+        };
+        // a pinned `31.1-jre` is about the `jre` line, not a pre release of `31.1`
+        let (input, variant) = match split_variant(input) {
+            (input, Some(suffix)) if variant.is_none() => (input, Some(suffix)),
+            (_, _) => (Cow::Borrowed(input), variant),
+        };
+        let input = input.as_ref();
+        let ranges = if ecosystem == Ecosystem::Maven && input.starts_with(['[', '(']) {
+            maven_ranges(input)?
+        } else {
+            input
+                .split("||")
+                .map(|range| parse_range(range, ecosystem))
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        // any version is allowed if one of the alternatives allows any version
+        let ranges = if ranges.contains(&VersionReq::STAR) {
+            Vec::new()
+        } else {
+            ranges
+        };
+        Ok(Self {
+            ranges,
+            pre_releases,
+            variant,
+        })
+    }
+
+    /// Whether `version` is allowed by any of the ranges.
+    pub fn matches(&self, version: &Version) -> bool {
+        if self.ranges.is_empty() {
+            // like `*` in semver, which does not match pre releases either
+            return version.pre.is_empty();
+        }
+        self.ranges.iter().any(|range| range.matches(version))
+    }
+
+    /// The alternative ranges, empty if any version is allowed.
+    pub fn ranges(&self) -> &[VersionReq] {
+        &self.ranges
+    }
+
+    /// Whether pre releases are included (`?pre`) or excluded (`?stable`) for this requirement,
+    /// or `None` if that is left to the caller.
+    pub fn pre_releases(&self) -> Option<bool> {
+        self.pre_releases
+    }
+
+    /// The variant line of releases this requirement is about, e.g. `jre` for `31.1-jre`.
+    pub fn variant(&self) -> Option<&str> {
+        self.variant.as_deref()
+    }
+
+    /// The same requirement for a variant line of releases, or for releases without a variant.
+    pub fn with_variant(mut self, variant: Option<String>) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// Whether pre releases are included for this requirement, if `default` is used when it does not say.
+    pub fn allows_pre_releases(&self, default: bool) -> bool {
+        self.pre_releases.unwrap_or(default)
+    }
+}
+
+impl Coordinates {
+    /// Parses a requirement with the meaning it has in the ecosystem of this package.
+    ///
+    /// All ecosystems share the syntax of [`Requirement::parse`], but differ in what a bare version like `1.2` means:
+    ///
+    /// - cargo: compatible versions, like `^1.2`
+    /// - npm: versions starting with it, like `1.2.x`
+    /// - maven: exactly that version, `1.2.0`. Maven version ranges like `[1.0,2.0)` or `(,1.0],[1.2,)` are supported as well.
+    /// - go: the minimum version of the same major version, like `>=1.2, <2`, also for `v0` modules
+    pub fn parse_requirement(&self, input: &str) -> Result<Requirement, semver::Error> {
+        let ecosystem = match self {
+            Coordinates::Maven { .. } => Ecosystem::Maven,
+            Coordinates::Cargo(_) => Ecosystem::Cargo,
+            Coordinates::Npm { .. } => Ecosystem::Npm,
+            Coordinates::Go { .. } | Coordinates::AnyGo(_) => Ecosystem::Go,
+        };
+        Requirement::parse_with(input, ecosystem)
+    }
+}
+
+/// How bare versions without an operator are understood.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Ecosystem {
+    Cargo,
+    Npm,
+    Maven,
+    Go,
+    /// Not an ecosystem, but calendar versions in any of them.
+    CalVer,
+}
+
+impl Ecosystem {
+    /// Removes a leading `v` and brings calendar versions into the form of the versions they are matched against.
+    fn version(self, version: &str) -> Cow<'_, str> {
+        let version = strip_v(version);
+        match self {
+            Ecosystem::CalVer => normalize_calver(version),
+            _ => Cow::Borrowed(version),
+        }
+    }
+
+    /// Rewrites a version without an operator into comparators with the meaning of the ecosystem.
+    fn bare(self, version: &str) -> String {
+        if version.contains(['*', 'x', 'X']) {
+            return version.into();
+        }
+        match self {
+            Ecosystem::Cargo => version.into(),
+            // `=1.2` already means `1.2.x` for partial versions
+            Ecosystem::Npm | Ecosystem::CalVer => format!("={}", version),
+            Ecosystem::Maven => format!("={}", full_version(version)),
+            Ecosystem::Go => {
+                let major = version.split(['.', '-', '+']).next().unwrap_or_default();
+                let next = major.parse::<u64>().map_or(1, |major| major + 1);
+                format!(">={}, <{}", version, next)
+            }
+        }
+    }
+}
+
+/// Parses Maven version ranges, where multiple sets are alternatives, e.g. `(,1.0],[1.2,)`.
+fn maven_ranges(input: &str) -> Result<Vec<VersionReq>, semver::Error> {
+    let mut ranges = Vec::new();
+    let mut rest = input;
+    while let Some(start) = rest.find(['[', '(']) {
+        let end = match rest[start..].find([']', ')']) {
+            Some(end) => start + end,
+            // unbalanced, let semver report the error
+            None => return Err(VersionReq::parse(rest).unwrap_err()),
+        };
+        let (open, set, close) = (
+            &rest[start..=start],
+            &rest[start + 1..end],
+            &rest[end..=end],
+        );
+        rest = &rest[end + 1..];
+
+        let range = match set.split_once(',') {
+            Some((lower, upper)) => {
+                let lower = lower.trim();
+                let upper = upper.trim();
+                let mut comparators = Vec::new();
+                if !lower.is_empty() {
+                    let op = if open == "[" { ">=" } else { ">" };
+                    comparators.push(format!("{}{}", op, full_version(lower)));
+                }
+                if !upper.is_empty() {
+                    let op = if close == "]" { "<=" } else { "<" };
+                    comparators.push(format!("{}{}", op, full_version(upper)));
+                }
+                if comparators.is_empty() {
+                    String::from("*")
+                } else {
+                    comparators.join(", ")
+                }
+            }
+            None => format!("={}", full_version(set.trim())),
+        };
+        ranges.push(VersionReq::parse(&range)?);
+    }
+    Ok(ranges)
+}
+
+/// Fills up missing minor and patch components with zeros, as `1.2` is the same version as `1.2.0` in Maven.
+fn full_version(version: &str) -> String {
+    let end = version.find(['-', '+']).unwrap_or(version.len());
+    let (numbers, rest) = version.split_at(end);
+    let missing = 2usize.saturating_sub(numbers.matches('.').count());
+    format!("{}{}{}", numbers, ".0".repeat(missing), rest)
+}
+
+/// Translates a single npm range into the comma separated comparators of cargo.
+fn parse_range(range: &str, ecosystem: Ecosystem) -> Result<VersionReq, semver::Error> {
+    let range = range.trim();
+    if let Some((lower, upper)) = range.split_once(" - ") {
+        // `<=1.2` already means `<1.3.0` for partial versions
+        return VersionReq::parse(&format!(
+            ">={}, <={}",
+            ecosystem.version(lower.trim()),
+            ecosystem.version(upper.trim())
+        ));
+    }
+
+    let mut comparators = Vec::<String>::new();
+    let mut operator = None::<&str>;
+    for token in range.split([' ', ',']).filter(|t| !t.is_empty()) {
+        if token
+            .chars()
+            .all(|c| matches!(c, '<' | '>' | '=' | '^' | '~'))
+        {
+            operator = Some(token);
+            continue;
+        }
+        let split = token
+            .find(|c: char| !matches!(c, '<' | '>' | '=' | '^' | '~'))
+            .unwrap_or(token.len());
+        let (op, version) = token.split_at(split);
+        let version = ecosystem.version(version);
+        comparators.push(match operator.take() {
+            Some(operator) => format!("{}{}", operator, version),
+            None if op.is_empty() => ecosystem.bare(&version),
+            None => format!("{}{}", op, version),
+        });
+    }
+    if let Some(operator) = operator {
+        // an operator without a version
+        return VersionReq::parse(operator);
+    }
+    if comparators.is_empty() {
+        return Ok(VersionReq::STAR);
+    }
+    VersionReq::parse(&comparators.join(", "))
+}
+
+/// Removes a known variant like `-jre` or `.Final` from the end of every version in a requirement,
+/// and returns the first of those variants.
+fn split_variant(input: &str) -> (Cow<'_, str>, Option<String>) {
+    let mut variant = None::<&str>;
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+    while !rest.is_empty() {
+        let end = rest
+            .find([' ', ',', '|', ']', ')'])
+            .map_or(rest.len(), |end| end.max(1));
+        let (token, tail) = rest.split_at(end);
+        rest = tail;
+        let suffix = token
+            .rfind(['-', '.'])
+            .filter(|&at| token[..at].ends_with(|c: char| c.is_ascii_digit()))
+            .map(|at| (&token[..at], &token[at + 1..]))
+            .filter(|(_, tag)| crate::versions::is_variant(tag));
+        match suffix {
+            Some((version, tag)) => {
+                output.push_str(version);
+                let _ = variant.get_or_insert(tag);
+            }
+            None => output.push_str(token),
+        }
+    }
+    match variant {
+        Some(variant) => (Cow::Owned(output), Some(variant.to_string())),
+        None => (Cow::Borrowed(input), None),
+    }
+}
+
+/// npm allows versions to start with a `v`, like `v1.2.3`.
+fn strip_v(version: &str) -> &str {
+    match version.strip_prefix(['v', 'V']) {
+        Some(stripped) if stripped.starts_with(|c: char| c.is_ascii_digit()) => stripped,
+        _ => version,
+    }
+}
+
+impl From<VersionReq> for Requirement {
+    fn from(range: VersionReq) -> Self {
+        if range == VersionReq::STAR {
+            Self::STAR
+        } else {
+            Self {
+                ranges: vec![range],
+                pre_releases: None,
+                variant: None,
+            }
+        }
+    }
+}
+
+impl FromStr for Requirement {
+    type Err = semver::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl Display for Requirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.ranges.is_empty() {
+            f.write_str("*")?;
+        }
+        for (idx, range) in self.ranges.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(" || ")?;
+            }
+            write!(f, "{}", range)?;
+        }
+        if let Some(variant) = &self.variant {
+            write!(f, "@{}", variant)?;
+        }
+        match self.pre_releases {
+            Some(true) => f.write_str("?pre"),
+            Some(false) => f.write_str("?stable"),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn matching(requirement: &str, versions: &[&str]) -> Vec<String> {
+        let requirement = Requirement::parse(requirement).unwrap();
+        versions
+            .iter()
+            .filter(|v| requirement.matches(&Version::parse(v).unwrap()))
+            .map(|v| v.to_string())
+            .collect()
+    }
+
+    const VERSIONS: &[&str] = &[
+        "0.9.0", "1.0.0", "1.2.0", "1.2.5", "1.3.0", "2.0.0", "2.3.0", "2.3.9", "2.4.0", "3.0.0",
+    ];
+
+    #[test_case("^1 || ^3", &["1.0.0", "1.2.0", "1.2.5", "1.3.0", "3.0.0"]; "alternatives")]
+    #[test_case("1.2 - 2.3", &["1.2.0", "1.2.5", "1.3.0", "2.0.0", "2.3.0", "2.3.9"]; "hyphen range with partial versions")]
+    #[test_case("1.2.5 - 2.3.0", &["1.2.5", "1.3.0", "2.0.0", "2.3.0"]; "hyphen range")]
+    #[test_case(">=1.2 <2", &["1.2.0", "1.2.5", "1.3.0"]; "space separated comparators")]
+    #[test_case(">= 1.2, < 2", &["1.2.0", "1.2.5", "1.3.0"]; "operators separated by spaces")]
+    #[test_case("1.2.x || >=3", &["1.2.0", "1.2.5", "3.0.0"]; "x range in alternatives")]
+    #[test_case("~v1.2.0", &["1.2.0", "1.2.5"]; "v prefix")]
+    #[test_case("<1 || *", VERSIONS; "star in alternatives")]
+    #[test_case("", VERSIONS; "empty")]
+    fn npm_ranges(requirement: &str, expected: &[&str]) {
+        assert_eq!(matching(requirement, VERSIONS), expected);
+    }
+
+    #[test_case("^1 || ^2", "^1 || ^2"; "alternatives")]
+    #[test_case("1.2 - 2.3", ">=1.2, <=2.3"; "hyphen range")]
+    #[test_case(">=1 <2", ">=1, <2"; "space separated")]
+    #[test_case("*", "*"; "star")]
+    #[test_case("^2 ?pre", "^2?pre"; "pre releases")]
+    #[test_case("?stable", "*?stable"; "stable star")]
+    #[test_case("^31 @jre ?pre", "^31@jre?pre"; "variant")]
+    #[test_case("^31.1-jre", "^31.1@jre"; "variant suffix")]
+    #[test_case(">=5.6.0.Final, <6", ">=5.6.0, <6@Final"; "variant suffix in build metadata")]
+    #[test_case("^1.0.0-rc.1", "^1.0.0-rc.1"; "pre release suffix")]
+    fn display(requirement: &str, expected: &str) {
+        assert_eq!(
+            Requirement::parse(requirement).unwrap().to_string(),
+            expected
+        );
+    }
+
+    #[test_case("^2?pre", Some(true); "pre")]
+    #[test_case("^2?stable", Some(false); "stable")]
+    #[test_case("^2", None; "default")]
+    fn pre_release_override(requirement: &str, expected: Option<bool>) {
+        let requirement = Requirement::parse(requirement).unwrap();
+        assert_eq!(requirement.pre_releases(), expected);
+        assert_eq!(requirement.ranges(), [VersionReq::parse("^2").unwrap()]);
+    }
+
+    fn ecosystem_matching(coordinates: Coordinates, requirement: &str) -> Vec<String> {
+        let requirement = coordinates.parse_requirement(requirement).unwrap();
+        VERSIONS
+            .iter()
+            .filter(|v| requirement.matches(&Version::parse(v).unwrap()))
+            .map(|v| v.to_string())
+            .collect()
+    }
+
+    fn maven() -> Coordinates {
+        Coordinates::Maven {
+            group_id: "org.neo4j".into(),
+            artifact_id: "neo4j".into(),
+        }
+    }
+
+    fn npm() -> Coordinates {
+        Coordinates::Npm {
+            scope: None,
+            package: "react".into(),
+        }
+    }
+
+    fn go() -> Coordinates {
+        Coordinates::AnyGo("golang.org/x/text".into())
+    }
+
+    #[test_case(Coordinates::Cargo("serde".into()), "1.2", &["1.2.0", "1.2.5", "1.3.0"]; "cargo caret")]
+    #[test_case(npm(), "1.2", &["1.2.0", "1.2.5"]; "npm partial")]
+    #[test_case(npm(), "2", &["2.0.0", "2.3.0", "2.3.9", "2.4.0"]; "npm major")]
+    #[test_case(npm(), "1.2.5", &["1.2.5"]; "npm exact")]
+    #[test_case(npm(), "^1.2 || 3", &["1.2.0", "1.2.5", "1.3.0", "3.0.0"]; "npm alternatives")]
+    #[test_case(maven(), "1.2", &["1.2.0"]; "maven pin")]
+    #[test_case(maven(), "1.x", &["1.0.0", "1.2.0", "1.2.5", "1.3.0"]; "maven wildcard")]
+    #[test_case(maven(), "[1.2,2.3)", &["1.2.0", "1.2.5", "1.3.0", "2.0.0"]; "maven range")]
+    #[test_case(maven(), "(1.2,2.3]", &["1.2.5", "1.3.0", "2.0.0", "2.3.0"]; "maven exclusive lower")]
+    #[test_case(maven(), "(,1.0],[2.4,)", &["0.9.0", "1.0.0", "2.4.0", "3.0.0"]; "maven multiple sets")]
+    #[test_case(maven(), "[2.3]", &["2.3.0"]; "maven exact range")]
+    #[test_case(go(), "v1.2.5", &["1.2.5", "1.3.0"]; "go minimum")]
+    #[test_case(go(), "0.9", &["0.9.0"]; "go v0")]
+    fn ecosystems(coordinates: Coordinates, requirement: &str, expected: &[&str]) {
+        assert_eq!(ecosystem_matching(coordinates, requirement), expected);
+    }
+
+    #[test_case(maven(), "[1.2,2.3"; "unbalanced maven range")]
+    #[test_case(maven(), "[1.2,qux]"; "invalid maven version")]
+    #[test_case(npm(), "1.2.3.4"; "invalid npm version")]
+    fn invalid_in_ecosystem(coordinates: Coordinates, requirement: &str) {
+        assert!(coordinates.parse_requirement(requirement).is_err());
+    }
+
+    #[test_case("2023", &["2023.1.5", "2023.10.17"]; "year")]
+    #[test_case("2023.*", &["2023.1.5", "2023.10.17"]; "year wildcard")]
+    #[test_case("23.10", &["2023.10.17"]; "short year")]
+    #[test_case(">=20230601", &["2023.10.17", "2024.2.1"]; "compact date")]
+    #[test_case("20230101 - 20231231", &["2023.1.5", "2023.10.17"]; "date range")]
+    fn calver(requirement: &str, expected: &[&str]) {
+        let requirement = Requirement::parse_calver(requirement).unwrap();
+        let matching = ["2022.12.31", "2023.1.5", "2023.10.17", "2024.2.1"]
+            .iter()
+            .filter(|v| requirement.matches(&Version::parse(v).unwrap()))
+            .copied()
+            .collect::<Vec<_>>();
+        assert_eq!(matching, expected);
+    }
+
+    #[test_case("1.2 -"; "incomplete hyphen range")]
+    #[test_case("^1 || qux"; "invalid alternative")]
+    #[test_case(">= "; "operator without version")]
+    #[test_case("^2?beta"; "unknown pre release override")]
+    #[test_case("^31@"; "empty variant")]
+    fn invalid(requirement: &str) {
+        assert!(Requirement::parse(requirement).is_err());
+    }
+}
@@ -0,0 +1,145 @@
+use semver::Version;
+use std::{borrow::Cow, fmt::Display, str::FromStr};
+
+/// How the versions of a package are read and compared.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VersionScheme {
+    /// Semantic versions, also when they are not exactly valid, like `1.2` or `1.2.3.4`.
+    #[default]
+    Semver,
+    /// Calendar versions like `2023.10.1`, `23.10`, or `20231017`, read as year, month, and day (or micro).
+    CalVer,
+}
+
+impl VersionScheme {
+    /// Reads a version in this scheme, `None` if it is not a version at all.
+    pub fn parse(self, version: &str) -> Option<Version> {
+        match self {
+            VersionScheme::Semver => lenient_semver::parse(version).ok(),
+            VersionScheme::CalVer => lenient_semver::parse(&normalize_calver(version)).ok(),
+        }
+    }
+
+    /// The date of a version as `YYYY-MM-DD`, if the scheme says anything about it.
+    ///
+    /// Only the year is reliable, the month and day fall back to the first if they are not within their range.
+    pub fn date(self, version: &Version) -> Option<String> {
+        match self {
+            VersionScheme::Semver => None,
+            VersionScheme::CalVer => {
+                let month = Some(version.minor).filter(|m| (1..=12).contains(m));
+                let day = month.and(Some(version.patch).filter(|d| (1..=31).contains(d)));
+                Some(format!(
+                    "{:04}-{:02}-{:02}",
+                    version.major,
+                    month.unwrap_or(1),
+                    day.unwrap_or(1)
+                ))
+            }
+        }
+    }
+}
+
+/// Rewrites compact dates like `20231017` to `2023.10.17`, and short years like `23.10` to `2023.10`.
+///
+/// Anything else is returned as is, also with a leading `v`.
+pub(crate) fn normalize_calver(input: &str) -> Cow<'_, str> {
+    let (prefix, version) = match input.strip_prefix(['v', 'V']) {
+        Some(stripped) => input.split_at(input.len() - stripped.len()),
+        None => ("", input),
+    };
+    let end = version
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(version.len());
+    let (date, rest) = version.split_at(end);
+    let number = |range: std::ops::Range<usize>| date[range].parse::<u32>().unwrap_or_default();
+    match date.len() {
+        8 => Cow::Owned(format!(
+            "{}{}.{}.{}{}",
+            prefix,
+            number(0..4),
+            number(4..6),
+            number(6..8),
+            rest
+        )),
+        6 => Cow::Owned(format!(
+            "{}{}.{}{}",
+            prefix,
+            number(0..4),
+            number(4..6),
+            rest
+        )),
+        1 | 2 => Cow::Owned(format!(
+            "{}{}{}",
+            prefix,
+            2000 + number(0..date.len()),
+            rest
+        )),
+        _ => Cow::Borrowed(input),
+    }
+}
+
+impl FromStr for VersionScheme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "semver" => Ok(VersionScheme::Semver),
+            "calver" => Ok(VersionScheme::CalVer),
+            _ => Err(format!(
+                "Unknown version scheme {}, expected one of semver or calver",
+                s
+            )),
+        }
+    }
+}
+
+impl Display for VersionScheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            VersionScheme::Semver => "semver",
+            VersionScheme::CalVer => "calver",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("20231017", "2023.10.17"; "compact date")]
+    #[test_case("20231017.1", "2023.10.17.1"; "compact date with build number")]
+    #[test_case("202310", "2023.10"; "compact month")]
+    #[test_case("23.10", "2023.10"; "short year")]
+    #[test_case("v23.4.1", "v2023.4.1"; "with v")]
+    #[test_case("2023.10.1", "2023.10.1"; "full year")]
+    #[test_case("2023.10.1-rc1", "2023.10.1-rc1"; "pre release")]
+    #[test_case("latest", "latest"; "not a version")]
+    fn normalize(version: &str, expected: &str) {
+        assert_eq!(normalize_calver(version), expected);
+    }
+
+    #[test_case("20231017", "2023-10-17"; "compact date")]
+    #[test_case("2023.10.1", "2023-10-01"; "year month day")]
+    #[test_case("2023.1.100", "2023-01-01"; "micro is not a day")]
+    #[test_case("2023.45", "2023-01-01"; "not a month")]
+    fn date(version: &str, expected: &str) {
+        let scheme = VersionScheme::CalVer;
+        let version = scheme.parse(version).unwrap();
+        assert_eq!(scheme.date(&version).as_deref(), Some(expected));
+    }
+
+    #[test]
+    fn compare() {
+        let scheme = VersionScheme::CalVer;
+        let old = scheme.parse("20230930").unwrap();
+        let new = scheme.parse("2023.10.1").unwrap();
+        assert!(old < new);
+        assert_eq!(
+            VersionScheme::Semver.date(&VersionScheme::Semver.parse("1.2").unwrap()),
+            None
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use color_eyre::eyre::Result;
+use console::style;
+use latest_version::{Coordinates, Requirement};
+use std::fmt::Write;
+use tokio::io::{self, AsyncWriteExt};
+
+/// Prints ready-to-paste declarations for every latest version.
+pub(crate) async fn report(
+    coordinates: &Coordinates,
+    latest: &[(Requirement, Option<String>)],
+) -> Result<()> {
+    let mut msg = String::with_capacity(128);
+    for version in latest.iter().filter_map(|(_, v)| v.as_deref()) {
+        write_declarations(&mut msg, coordinates, version);
+    }
+    io::stdout().write_all(msg.as_bytes()).await?;
+    Ok(())
+}
+
+fn write_declarations(msg: &mut String, coordinates: &Coordinates, version: &str) {
+    for declaration in coordinates.declarations(version) {
+        let _ = writeln!(msg, "  {}:", style(declaration.format).dim());
+        for line in declaration.text.lines() {
+            let _ = writeln!(msg, "    {}", style(line).green());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indented_declarations() {
+        console::set_colors_enabled(false);
+        let mut msg = String::new();
+        write_declarations(&mut msg, &Coordinates::Cargo("serde".into()), "1.0.150");
+        assert_eq!(msg, "  Cargo.toml:\n    serde = \"1.0.150\"\n");
+    }
+}
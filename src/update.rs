@@ -0,0 +1,160 @@
+use crate::{find_latest_versions, Config};
+use color_eyre::eyre::{Result, WrapErr};
+use console::style;
+use latest_version::{Client, Dependency, Manifest, Requirement};
+use std::{fmt::Write, fs, sync::Arc};
+use tokio::io::{self, AsyncWriteExt};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Bump {
+    /// Only bump to the latest version that still matches the current requirement.
+    Compatible,
+    /// Bump to the latest version, even if it does not match the current requirement.
+    Latest,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Update {
+    pub(crate) bump: Bump,
+    pub(crate) dry_run: bool,
+}
+
+/// Rewrites the requirements in the manifests to the latest versions.
+pub(crate) async fn run(
+    client: Client,
+    config: Config,
+    manifests: Vec<Manifest>,
+    update: Update,
+) -> Result<()> {
+    let client = Arc::new(client);
+    for manifest in manifests {
+        let tasks = manifest
+            .dependencies()
+            .iter()
+            .cloned()
+            .map(|dep| {
+                let client = Arc::clone(&client);
+                let config = config.clone();
+                tokio::spawn(
+                    async move { bumped_requirement(&client, &config, update, &dep).await },
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut updates = Vec::new();
+        for (task, dep) in tasks.into_iter().zip(manifest.dependencies()) {
+            if let Some(requirement) = task.await?? {
+                updates.push((dep, requirement));
+            }
+        }
+
+        report(&manifest, &updates, update).await?;
+
+        if !updates.is_empty() && !update.dry_run {
+            if let Some(path) = manifest.path() {
+                let content =
+                    manifest.with_requirements(updates.iter().map(|(d, r)| (*d, r.as_str())));
+                fs::write(path, content)
+                    .wrap_err_with(|| format!("Could not write {}", path.display()))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn bumped_requirement(
+    client: &Client,
+    config: &Config,
+    update: Update,
+    dep: &Dependency,
+) -> Result<Option<String>> {
+    let requirement = match update.bump {
+        Bump::Compatible => dep.requirement.clone(),
+        // stay on the line of a pinned `31.1-jre`
+        Bump::Latest => Requirement::STAR.with_variant(dep.requirement.variant().map(String::from)),
+    };
+    let versions = client.query_versions(&dep.coordinates).await?;
+    let latest = find_latest_versions(
+        client,
+        config,
+        &dep.coordinates,
+        versions,
+        vec![requirement],
+    )
+    .await?
+    .0
+    .pop()
+    .and_then(|(_, latest)| latest);
+
+    Ok(latest.and_then(|latest| dep.bumped_requirement(&latest)))
+}
+
+async fn report(
+    manifest: &Manifest,
+    updates: &[(&Dependency, String)],
+    update: Update,
+) -> Result<()> {
+    let mut msg = String::with_capacity(256);
+    let path = manifest
+        .path()
+        .map_or_else(|| String::from("manifest"), |p| p.display().to_string());
+
+    if updates.is_empty() {
+        let _ = writeln!(
+            msg,
+            "All requirements in {} are up to date",
+            style(&path).bold()
+        );
+    } else if update.dry_run {
+        let _ = writeln!(msg, "Would update {}", style(&path).bold());
+        let updated = manifest.with_requirements(updates.iter().map(|(d, r)| (*d, r.as_str())));
+        write_diff(&mut msg, manifest.content(), &updated);
+    } else {
+        let _ = writeln!(msg, "Updated {}", style(&path).bold());
+        for (dep, requirement) in updates {
+            let _ = writeln!(
+                msg,
+                "  {}: {} -> {}",
+                style(&dep.name).blue(),
+                style(dep.raw_requirement()).yellow(),
+                style(requirement).green().bold()
+            );
+        }
+    }
+
+    io::stdout().write_all(msg.as_bytes()).await?;
+    Ok(())
+}
+
+/// Writes the changed lines, the requirements are replaced in place, so the line count stays the same.
+fn write_diff(msg: &mut String, old: &str, new: &str) {
+    for (line_no, (old, new)) in old.lines().zip(new.lines()).enumerate() {
+        if old != new {
+            let _ = writeln!(
+                msg,
+                "{}",
+                style(format!("@@ line {} @@", line_no + 1)).cyan()
+            );
+            let _ = writeln!(msg, "{}", style(format!("-{}", old)).red());
+            let _ = writeln!(msg, "{}", style(format!("+{}", new)).green());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_only_changed_lines() {
+        console::set_colors_enabled(false);
+        let mut msg = String::new();
+        write_diff(
+            &mut msg,
+            "[dependencies]\nfoo = \"1.0\"\nbar = \"2\"\n",
+            "[dependencies]\nfoo = \"1.4.2\"\nbar = \"2\"\n",
+        );
+        assert_eq!(msg, "@@ line 2 @@\n-foo = \"1.0\"\n+foo = \"1.4.2\"\n");
+    }
+}
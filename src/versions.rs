@@ -1,80 +1,563 @@
-use semver::{Version, VersionReq};
-use std::iter::FromIterator;
+use crate::{Requirement, VersionFilter, VersionScheme};
+use semver::{Prerelease, Version};
+use serde_json::Value;
+use std::{
+    cmp::Ordering,
+    iter::FromIterator,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
+/// All published versions of a package.
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
-pub(crate) struct Versions {
-    version: Vec<String>,
+pub struct Versions {
+    version: Vec<Release>,
+    include_withdrawn: bool,
+    pre_release_channel: Option<PreReleaseChannel>,
+    scheme: VersionScheme,
+    /// The earliest date, as `YYYY-MM-DD`, of the versions that are considered.
+    released_since: Option<String>,
+    natural_fallback: bool,
+}
+
+/// A published version together with what the registry knows about it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Release {
+    pub version: String,
+    /// When the version was published, as an RFC 3339 timestamp.
+    pub published_at: Option<String>,
+    pub status: ReleaseStatus,
+}
+
+/// The channel of a pre release, ordered from the least to the most stable one.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PreReleaseChannel {
+    /// `-SNAPSHOT`, `-nightly`, `-dev`, or `-canary` builds
+    Nightly,
+    /// `-alpha` or `-a`
+    Alpha,
+    /// `-beta`, `-b`, or `-preview`
+    Beta,
+    /// Maven milestones, `-M1` or `-milestone`
+    Milestone,
+    /// `-rc` or `-cr`
+    Rc,
+}
+
+/// Whether a version was withdrawn by its authors.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReleaseStatus {
+    Published,
+    /// Yanked from crates.io
+    Yanked,
+    /// Deprecated on npm
+    Deprecated,
+    /// Retracted in a go.mod
+    Retracted,
+}
+
+impl Release {
+    fn new(version: String) -> Self {
+        Self {
+            version,
+            published_at: None,
+            status: ReleaseStatus::Published,
+        }
+    }
+
+    /// Reads a version from an entry of the versions endpoint, unknown fields are ignored.
+    pub(crate) fn from_json(entry: &Value) -> Option<Self> {
+        let version = entry
+            .get("version")
+            .or_else(|| entry.get("versionKey")?.get("version"))
+            .and_then(Value::as_str)?;
+
+        let published_at = ["publishedAt", "createdAt"]
+            .iter()
+            .filter_map(|key| entry.get(key))
+            .find_map(|published| match published {
+                Value::String(s) if !s.is_empty() => Some(s.clone()),
+                Value::Number(n) => n.as_u64().map(format_timestamp),
+                _ => None,
+            });
+
+        let flag = |keys: &[&str]| {
+            keys.iter()
+                .any(|key| entry.get(key).and_then(Value::as_bool) == Some(true))
+        };
+        let status = if flag(&["yanked", "isYanked"]) {
+            ReleaseStatus::Yanked
+        } else if flag(&["deprecated", "isDeprecated"]) {
+            ReleaseStatus::Deprecated
+        } else if flag(&["retracted", "isRetracted"]) {
+            ReleaseStatus::Retracted
+        } else {
+            ReleaseStatus::Published
+        };
+
+        Some(Self {
+            version: version.into(),
+            published_at,
+            status,
+        })
+    }
+
+    /// The day the version was published, e.g. `2022-11-05`.
+    pub fn published_on(&self) -> Option<&str> {
+        self.published_at.as_deref().and_then(|p| p.get(..10))
+    }
+}
+
+impl PreReleaseChannel {
+    /// Detects the channel from a tag like `rc`, `alpha02`, or `SNAPSHOT`, ignoring any trailing numbers.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let tag = tag
+            .split(|c: char| !c.is_ascii_alphabetic())
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match tag.as_str() {
+            "snapshot" | "nightly" | "dev" | "canary" => Some(Self::Nightly),
+            "alpha" | "a" => Some(Self::Alpha),
+            "beta" | "b" | "preview" => Some(Self::Beta),
+            "m" | "milestone" => Some(Self::Milestone),
+            "rc" | "cr" => Some(Self::Rc),
+            _ => None,
+        }
+    }
+
+    /// The channel of a pre release, `None` if the tag is not known.
+    pub fn of(pre: &Prerelease) -> Option<Self> {
+        Self::from_tag(pre.as_str())
+    }
+}
+
+/// Formats seconds since the unix epoch as an RFC 3339 timestamp in UTC.
+fn format_timestamp(secs: u64) -> String {
+    let days = (secs / 86400) as i64;
+    let secs = secs % 86400;
+
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    )
+}
+
+impl FromIterator<Release> for Versions {
+    fn from_iter<T: IntoIterator<Item = Release>>(iter: T) -> Self {
+        let version = iter.into_iter().collect();
+        Versions {
+            version,
+            ..Versions::default()
+        }
+    }
 }
 
 impl FromIterator<String> for Versions {
     fn from_iter<T: IntoIterator<Item = String>>(iter: T) -> Self {
-        let version = iter.into_iter().collect();
-        Versions { version }
+        iter.into_iter().map(Release::new).collect()
     }
 }
 
 impl<'a> FromIterator<&'a str> for Versions {
     fn from_iter<T: IntoIterator<Item = &'a str>>(iter: T) -> Self {
-        let version = iter.into_iter().map(String::from).collect();
-        Versions { version }
+        iter.into_iter().map(String::from).collect()
     }
 }
 
 impl Versions {
-    pub(crate) fn latest_versions(
+    /// Whether yanked, deprecated, or retracted versions can be latest versions.
+    ///
+    /// They are skipped by default.
+    pub fn include_withdrawn(mut self, include: bool) -> Self {
+        self.include_withdrawn = include;
+        self
+    }
+
+    /// Only consider pre releases of `channel` or a more stable channel, e.g. `rc`, but not `alpha`, for [`PreReleaseChannel::Beta`].
+    ///
+    /// Pre releases with tags that are not known are skipped as well. Without a channel, all pre releases are considered.
+    /// This does not include pre releases by itself, they still need to be allowed by the caller or a requirement.
+    pub fn pre_release_channel(mut self, channel: Option<PreReleaseChannel>) -> Self {
+        self.pre_release_channel = channel;
+        self
+    }
+
+    /// Reads and compares versions in `scheme`, e.g. to treat `20231017` as `2023.10.17`.
+    pub fn scheme(mut self, scheme: VersionScheme) -> Self {
+        self.scheme = scheme;
+        self
+    }
+
+    /// Only consider versions that were released on or after `date`, given as `YYYY-MM-DD`.
+    ///
+    /// For calendar versions, this is the date of the version, otherwise the date it was published.
+    /// Versions without a known date are skipped.
+    pub fn released_since(mut self, date: impl Into<String>) -> Self {
+        self.released_since = Some(date.into());
+        self
+    }
+
+    /// Only consider versions that were released within the last `duration`, see [`Versions::released_since`].
+    pub fn released_within(self, duration: Duration) -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let since = now.saturating_sub(duration).as_secs();
+        let mut date = format_timestamp(since);
+        date.truncate(10);
+        self.released_since(date)
+    }
+
+    /// Whether versions that cannot be parsed are ordered naturally, e.g. `r10` after `r9`,
+    /// as a fallback for requirements that allow any version and the latest version overall.
+    ///
+    /// They are only used if no version that can be parsed is found, and are skipped by default.
+    pub fn natural_fallback(mut self, fallback: bool) -> Self {
+        self.natural_fallback = fallback;
+        self
+    }
+
+    /// The versions that cannot be parsed in the scheme of these versions, in the order they were published.
+    ///
+    /// Those versions never match a requirement, see [`Versions::natural_fallback`].
+    pub fn unparsed(&self) -> Vec<&str> {
+        self.version
+            .iter()
+            .map(|release| release.version.as_str())
+            .filter(|version| self.scheme.parse(version).is_none())
+            .collect()
+    }
+
+    /// Removes all versions that are not kept by `filter`, before any requirement is matched.
+    pub fn filter(mut self, filter: &VersionFilter) -> Self {
+        if !filter.is_empty() {
+            self.version
+                .retain(|release| filter.matches(&release.version));
+        }
+        self
+    }
+
+    /// Finds the latest version for each requirement.
+    ///
+    /// Every version is assigned to the first requirement that it matches.
+    /// Without any requirements, the latest overall version is returned.
+    /// Pre releases are only considered if `allow_pre_release` is set,
+    /// unless a requirement overrides this with `?pre` or `?stable`.
+    /// Variants like `31.1-jre` are treated as releases, a requirement with a variant only matches versions of that variant.
+    /// Among equal versions of different variants, the one without a variant is preferred, then the first variant alphabetically.
+    pub fn latest_versions(
         self,
         allow_pre_release: bool,
-        mut requirements: Vec<VersionReq>,
-    ) -> Vec<(VersionReq, Option<String>)> {
+        mut requirements: Vec<Requirement>,
+    ) -> Vec<(Requirement, Option<String>)> {
         if requirements.is_empty() {
-            requirements.push(VersionReq::STAR);
+            requirements.push(Requirement::STAR);
         }
         let latest = self.find_latest_versions(&requirements[..], allow_pre_release);
-        requirements.into_iter().zip(latest.into_iter()).collect()
+        requirements.into_iter().zip(latest).collect()
+    }
+
+    /// Like [`Versions::latest_versions`], but also finds the latest version overall,
+    /// regardless of whether it matches any requirement.
+    /// If all requirements are about the same variant, the latest version overall is of that variant as well.
+    pub fn latest_versions_and_overall(
+        self,
+        allow_pre_release: bool,
+        mut requirements: Vec<Requirement>,
+    ) -> (Vec<(Requirement, Option<String>)>, Option<String>) {
+        if requirements.is_empty() {
+            requirements.push(Requirement::STAR);
+        }
+        let (latest, overall) = self.find_latest(&requirements[..], allow_pre_release);
+        (requirements.into_iter().zip(latest).collect(), overall)
+    }
+
+    /// The distinct variants of all versions, like `jre` and `android` for guava, in alphabetical order.
+    pub fn variants(&self) -> Vec<String> {
+        let mut variants = self
+            .version
+            .iter()
+            .filter_map(|release| self.scheme.parse(&release.version))
+            .filter_map(|v| variant(&v).map(String::from))
+            .collect::<Vec<_>>();
+        variants.sort();
+        variants.dedup();
+        variants
+    }
+
+    /// Removes a version, so that it is never selected as a latest version.
+    pub fn remove(&mut self, version: &str) {
+        self.version.retain(|v| v.version != version);
+    }
+
+    /// All releases that are newer than `from` and not newer than `to`, ordered from oldest to newest.
+    ///
+    /// Versions that cannot be parsed are skipped, as are pre releases unless `allow_pre_release` is set.
+    pub fn between(&self, from: &Version, to: &Version, allow_pre_release: bool) -> Vec<&Release> {
+        let mut releases = self
+            .version
+            .iter()
+            .filter_map(|release| Some((self.scheme.parse(&release.version)?, release)))
+            .filter(|(v, release)| self.is_recent(release, v))
+            .filter(|(v, _)| allow_pre_release || v.pre.is_empty() || variant(v).is_some())
+            .filter(|(v, _)| self.is_in_channel(v))
+            .filter(|(v, _)| v > from && v <= to)
+            .collect::<Vec<_>>();
+        releases.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+        releases.into_iter().map(|(_, release)| release).collect()
     }
 
     fn find_latest_versions(
         self,
-        requirements: &[VersionReq],
+        requirements: &[Requirement],
         allow_pre_release: bool,
     ) -> Vec<Option<String>> {
-        let mut latest_versions = vec![None; requirements.len()];
+        self.find_latest(requirements, allow_pre_release).0
+    }
+
+    fn find_latest(
+        self,
+        requirements: &[Requirement],
+        allow_pre_release: bool,
+    ) -> (Vec<Option<String>>, Option<String>) {
+        let mut latest_versions = vec![None::<(Version, Option<String>)>; requirements.len()];
         let mut best_matches = vec![None; requirements.len()];
+        let mut latest_overall = None::<(Version, Option<String>, String)>;
+        let channel = self.pre_release_channel;
+        // a `31.1-jre` pin should not be told about a newer `-android` release
+        let overall_variant =
+            requirements
+                .first()
+                .and_then(Requirement::variant)
+                .filter(|wanted| {
+                    requirements
+                        .iter()
+                        .all(|r| r.variant().is_some_and(|v| v.eq_ignore_ascii_case(wanted)))
+                });
+
+        let scheme = self.scheme;
+        let released_since = self.released_since.as_deref();
+        let mut unparsed = Vec::new();
 
-        for version in self.version {
-            if let Ok(v) = lenient_semver::parse(&version) {
-                let version_matches = if allow_pre_release {
-                    let version = Version::new(v.major, v.minor, v.patch);
-                    requirements.iter().position(|r| r.matches(&version))
-                } else {
-                    requirements.iter().position(|r| r.matches(&v))
+        for release in self.version {
+            if release.status != ReleaseStatus::Published && !self.include_withdrawn {
+                continue;
+            }
+            if let Some(v) = scheme.parse(&release.version) {
+                if !is_released_since(released_since, scheme, &release, &v) {
+                    continue;
+                }
+                let version = release.version;
+                // variants like `31.1-jre` are releases of their own line, not pre releases of `31.1`
+                let variant = variant(&v).map(String::from);
+                let v = match variant {
+                    Some(_) => Version::new(v.major, v.minor, v.patch),
+                    None => v,
                 };
+                if !in_channel(channel, &v) {
+                    continue;
+                }
+                let in_overall_variant =
+                    overall_variant.is_none_or(|wanted| is_of_variant(variant.as_deref(), wanted));
+                if in_overall_variant && (allow_pre_release || v.pre.is_empty()) {
+                    match &latest_overall {
+                        Some((overall, overall_variant, _))
+                            if !is_preferred(
+                                (&v, variant.as_deref()),
+                                (overall, overall_variant.as_deref()),
+                            ) => {}
+                        _ => latest_overall = Some((v.clone(), variant.clone(), version.clone())),
+                    }
+                }
+                let release = Version::new(v.major, v.minor, v.patch);
+                let version_matches = requirements.iter().position(|r| {
+                    if r.variant()
+                        .is_some_and(|wanted| !is_of_variant(variant.as_deref(), wanted))
+                    {
+                        false
+                    } else if r.allows_pre_releases(allow_pre_release) {
+                        r.matches(&release)
+                    } else {
+                        r.matches(&v)
+                    }
+                });
                 if let Some(pos) = version_matches {
-                    match &mut latest_versions[pos] {
-                        Some(existing) => {
-                            if v > *existing {
-                                *existing = v;
-                                best_matches[pos] = Some(version);
-                            }
-                        }
-                        entry @ None => {
-                            *entry = Some(v);
-                            best_matches[pos] = Some(version);
-                        }
+                    let preferred = match &latest_versions[pos] {
+                        Some((existing, existing_variant)) => is_preferred(
+                            (&v, variant.as_deref()),
+                            (existing, existing_variant.as_deref()),
+                        ),
+                        None => true,
+                    };
+                    if preferred {
+                        latest_versions[pos] = Some((v, variant));
+                        best_matches[pos] = Some(version);
                     }
                 }
+            } else if self.natural_fallback
+                && released_since
+                    .is_none_or(|since| release.published_on().is_some_and(|d| d >= since))
+            {
+                unparsed.push(release.version);
+            }
+        }
+
+        let mut latest_overall = latest_overall.map(|(_, _, version)| version);
+        if let Some(fallback) = unparsed
+            .into_iter()
+            .max_by(|lhs, rhs| natural_cmp(lhs, rhs))
+        {
+            let any_version = requirements
+                .iter()
+                .position(|r| r.ranges().is_empty() && r.variant().is_none());
+            if let Some(pos) = any_version {
+                let _ = best_matches[pos].get_or_insert_with(|| fallback.clone());
+            }
+            if overall_variant.is_none() {
+                let _ = latest_overall.get_or_insert(fallback);
             }
         }
 
-        best_matches
+        (best_matches, latest_overall)
+    }
+
+    fn is_in_channel(&self, version: &Version) -> bool {
+        in_channel(self.pre_release_channel, version)
+    }
+
+    fn is_recent(&self, release: &Release, version: &Version) -> bool {
+        is_released_since(
+            self.released_since.as_deref(),
+            self.scheme,
+            release,
+            version,
+        )
+    }
+}
+
+fn is_released_since(
+    since: Option<&str>,
+    scheme: VersionScheme,
+    release: &Release,
+    version: &Version,
+) -> bool {
+    let since = match since {
+        Some(since) => since,
+        None => return true,
+    };
+    match scheme.date(version) {
+        Some(date) => date.as_str() >= since,
+        None => release.published_on().is_some_and(|date| date >= since),
+    }
+}
+
+/// Compares runs of digits by their number and anything else as text, so that `r10` comes after `r9`.
+fn natural_cmp(lhs: &str, rhs: &str) -> Ordering {
+    fn chunks(s: &str) -> impl Iterator<Item = &str> {
+        let mut rest = s;
+        std::iter::from_fn(move || {
+            let digits = rest.chars().next()?.is_ascii_digit();
+            let end = rest
+                .find(|c: char| c.is_ascii_digit() != digits)
+                .unwrap_or(rest.len());
+            let (chunk, tail) = rest.split_at(end);
+            rest = tail;
+            Some(chunk)
+        })
+    }
+
+    let is_number = |chunk: &str| chunk.starts_with(|c: char| c.is_ascii_digit());
+    let mut lhs = chunks(lhs);
+    let mut rhs = chunks(rhs);
+    loop {
+        let ordering = match (lhs.next(), rhs.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => Ordering::Less,
+            (Some(_), None) => Ordering::Greater,
+            (Some(l), Some(r)) if is_number(l) && is_number(r) => {
+                let (l, r) = (l.trim_start_matches('0'), r.trim_start_matches('0'));
+                l.len().cmp(&r.len()).then_with(|| l.cmp(r))
+            }
+            (Some(l), Some(r)) => l.cmp(r),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+/// Tags, compared ignoring case, that mark a release line instead of a pre release.
+const VARIANTS: &[&str] = &["android", "final", "ga", "jre", "release"];
+
+/// Whether `tag` is one of the known [`VARIANTS`].
+pub(crate) fn is_variant(tag: &str) -> bool {
+    VARIANTS
+        .iter()
+        .any(|variant| tag.eq_ignore_ascii_case(variant))
+}
+
+/// The variant of a version like `31.1-jre`, `31.1-android`, or `5.6.0.Final`.
+///
+/// Only the tags in [`VARIANTS`] are variants, any other tag like `-ea` or `-next` stays a pre release.
+/// Lenient parsing puts some of them, like `Final` or `RELEASE`, into the build metadata instead of the pre release.
+pub(crate) fn variant(version: &Version) -> Option<&str> {
+    let tag = match version.pre.as_str() {
+        "" => version.build.as_str(),
+        pre => pre,
+    };
+    is_variant(tag).then_some(tag)
+}
+
+/// Whether a version of `variant` belongs to the `wanted` variant line.
+fn is_of_variant(variant: Option<&str>, wanted: &str) -> bool {
+    variant.is_some_and(|variant| variant.eq_ignore_ascii_case(wanted))
+}
+
+/// Whether `version` of `variant` is preferred over `existing` of `existing_variant`.
+///
+/// For the same version, the one without a variant wins, then the variant that comes first alphabetically,
+/// so that the result does not depend on the order in which versions are listed.
+fn is_preferred(
+    (version, variant): (&Version, Option<&str>),
+    (existing, existing_variant): (&Version, Option<&str>),
+) -> bool {
+    match version.cmp(existing) {
+        Ordering::Equal => variant < existing_variant,
+        ordering => ordering == Ordering::Greater,
+    }
+}
+
+fn in_channel(channel: Option<PreReleaseChannel>, version: &Version) -> bool {
+    match channel {
+        Some(channel) if !version.pre.is_empty() && variant(version).is_none() => {
+            PreReleaseChannel::of(&version.pre).is_some_and(|c| c >= channel)
+        }
+        _ => true,
     }
 }
 
 #[cfg(test)]
 impl From<&str> for Versions {
     fn from(version: &str) -> Self {
-        let version = vec![version.to_string()];
-        Self { version }
+        std::iter::once(version).collect()
     }
 }
 
@@ -84,8 +567,7 @@ where
     T: ToString,
 {
     fn from(items: &[T]) -> Self {
-        let version = items.iter().map(|x| x.to_string()).collect::<Vec<_>>();
-        Self { version }
+        items.iter().map(|x| x.to_string()).collect()
     }
 }
 
@@ -95,14 +577,14 @@ where
     T: Into<String>,
 {
     fn from(items: Vec<T>) -> Self {
-        let version = items.into_iter().map(Into::into).collect::<Vec<_>>();
-        Self { version }
+        items.into_iter().map(Into::into).collect()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use test_case::test_case;
 
     #[test]
     fn test_empty_reqs() {
@@ -114,7 +596,7 @@ mod tests {
     fn test_empty_versions() {
         let versions = Versions::from(Vec::<String>::new());
         assert_eq!(
-            versions.find_latest_versions(&[VersionReq::STAR], false),
+            versions.find_latest_versions(&[Requirement::STAR], false),
             vec![None]
         );
     }
@@ -123,7 +605,7 @@ mod tests {
     fn match_single_version() {
         let versions = Versions::from("1.0.0");
         assert_eq!(
-            versions.find_latest_versions(&[VersionReq::STAR], false),
+            versions.find_latest_versions(&[Requirement::STAR], false),
             vec![Some(String::from("1.0.0"))]
         );
     }
@@ -132,7 +614,7 @@ mod tests {
     fn select_latest() {
         let versions = Versions::from(["1.0.0", "1.3.37"].as_ref());
         assert_eq!(
-            versions.find_latest_versions(&[VersionReq::STAR], false),
+            versions.find_latest_versions(&[Requirement::STAR], false),
             vec![Some(String::from("1.3.37"))]
         );
     }
@@ -141,7 +623,7 @@ mod tests {
     fn lenient_version_parsing() {
         let versions = Versions::from(["1.0.0", "1.337"].as_ref());
         assert_eq!(
-            versions.find_latest_versions(&[VersionReq::STAR], false),
+            versions.find_latest_versions(&[Requirement::STAR], false),
             vec![Some(String::from("1.337"))]
         );
     }
@@ -152,8 +634,8 @@ mod tests {
         assert_eq!(
             versions.find_latest_versions(
                 &[
-                    VersionReq::parse("1.x").unwrap(),
-                    VersionReq::parse("2.x").unwrap()
+                    Requirement::parse("1.x").unwrap(),
+                    Requirement::parse("2.x").unwrap()
                 ],
                 false
             ),
@@ -167,9 +649,9 @@ mod tests {
         assert_eq!(
             versions.find_latest_versions(
                 &[
-                    VersionReq::parse("1.x").unwrap(),
-                    VersionReq::parse("42.x").unwrap(),
-                    VersionReq::parse("2.x").unwrap()
+                    Requirement::parse("1.x").unwrap(),
+                    Requirement::parse("42.x").unwrap(),
+                    Requirement::parse("2.x").unwrap()
                 ],
                 false
             ),
@@ -187,8 +669,8 @@ mod tests {
         assert_eq!(
             versions.find_latest_versions(
                 &[
-                    VersionReq::parse("^1").unwrap(),
-                    VersionReq::parse("1.2.3").unwrap(),
+                    Requirement::parse("^1").unwrap(),
+                    Requirement::parse("1.2.3").unwrap(),
                 ],
                 false
             ),
@@ -196,11 +678,111 @@ mod tests {
         );
     }
 
+    #[test]
+    fn removed_version() {
+        let mut versions = Versions::from(["1.0.0", "1.1.0", "1.2.0"].as_ref());
+        versions.remove("1.2.0");
+        assert_eq!(
+            versions.find_latest_versions(&[Requirement::STAR], false),
+            vec![Some(String::from("1.1.0"))]
+        );
+    }
+
+    #[test]
+    fn latest_overall() {
+        let versions = Versions::from(["1.9.3", "3.2.0", "2.0.0", "4.0.0-rc.1"].as_ref());
+        let (latest, overall) = versions
+            .clone()
+            .latest_versions_and_overall(false, vec![Requirement::parse("^1").unwrap()]);
+        assert_eq!(latest[0].1.as_deref(), Some("1.9.3"));
+        assert_eq!(overall.as_deref(), Some("3.2.0"));
+
+        let (_, overall) = versions.latest_versions_and_overall(true, vec![]);
+        assert_eq!(overall.as_deref(), Some("4.0.0-rc.1"));
+    }
+
+    #[test]
+    fn skip_withdrawn() {
+        let versions = ["1.0.0", "1.1.0"]
+            .iter()
+            .map(|v| {
+                let mut release = Release::new(v.to_string());
+                if *v == "1.1.0" {
+                    release.status = ReleaseStatus::Yanked;
+                }
+                release
+            })
+            .collect::<Versions>();
+        assert_eq!(
+            versions
+                .clone()
+                .find_latest_versions(&[Requirement::STAR], false),
+            vec![Some(String::from("1.0.0"))]
+        );
+        assert_eq!(
+            versions
+                .include_withdrawn(true)
+                .find_latest_versions(&[Requirement::STAR], false),
+            vec![Some(String::from("1.1.0"))]
+        );
+    }
+
+    #[test]
+    fn releases_between() {
+        let versions = Versions::from(
+            [
+                "1.0.150",
+                "1.0.99",
+                "1.0.100",
+                "1.0.120-rc.1",
+                "1.0.101",
+                "2.0.0",
+            ]
+            .as_ref(),
+        );
+        let from = Version::new(1, 0, 100);
+        let to = Version::new(1, 0, 150);
+        let between = |pre| {
+            versions
+                .between(&from, &to, pre)
+                .into_iter()
+                .map(|r| r.version.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(between(false), vec!["1.0.101", "1.0.150"]);
+        assert_eq!(between(true), vec!["1.0.101", "1.0.120-rc.1", "1.0.150"]);
+    }
+
+    #[test]
+    fn release_from_json() {
+        let release = Release::from_json(&serde_json::json!({
+            "version": "0.1.2",
+            "publishedAt": 1667649600,
+            "isDeprecated": true
+        }))
+        .unwrap();
+        assert_eq!(
+            release.published_at.as_deref(),
+            Some("2022-11-05T12:00:00Z")
+        );
+        assert_eq!(release.published_on(), Some("2022-11-05"));
+        assert_eq!(release.status, ReleaseStatus::Deprecated);
+
+        let release = Release::from_json(&serde_json::json!({
+            "versionKey": { "version": "1.0.0" },
+            "publishedAt": "2019-07-01T10:00:00Z"
+        }))
+        .unwrap();
+        assert_eq!(release.version, "1.0.0");
+        assert_eq!(release.published_on(), Some("2019-07-01"));
+        assert_eq!(release.status, ReleaseStatus::Published);
+    }
+
     #[test]
     fn skip_prerelease() {
         let versions = Versions::from(["1.0.0", "1.1.0-alpha01"].as_ref());
         assert_eq!(
-            versions.find_latest_versions(&[VersionReq::parse("^1").unwrap(),], false),
+            versions.find_latest_versions(&[Requirement::parse("^1").unwrap(),], false),
             vec![Some(String::from("1.0.0"))]
         );
     }
@@ -209,8 +791,308 @@ mod tests {
     fn include_prerelease() {
         let versions = Versions::from(["1.0.0", "1.1.0-alpha01"].as_ref());
         assert_eq!(
-            versions.find_latest_versions(&[VersionReq::parse("^1").unwrap(),], true),
+            versions.find_latest_versions(&[Requirement::parse("^1").unwrap(),], true),
             vec![Some(String::from("1.1.0-alpha01"))]
         );
     }
+
+    #[test]
+    fn prerelease_per_requirement() {
+        let versions = Versions::from(["1.0.0", "1.1.0-alpha01", "2.0.0-rc.1"].as_ref());
+        let requirements = [
+            Requirement::parse("^1").unwrap(),
+            Requirement::parse("^2?pre").unwrap(),
+        ];
+        assert_eq!(
+            versions.find_latest_versions(&requirements, false),
+            vec![
+                Some(String::from("1.0.0")),
+                Some(String::from("2.0.0-rc.1"))
+            ]
+        );
+
+        let versions = Versions::from(["1.0.0", "1.1.0-alpha01"].as_ref());
+        assert_eq!(
+            versions.find_latest_versions(&[Requirement::parse("^1?stable").unwrap()], true),
+            vec![Some(String::from("1.0.0"))]
+        );
+    }
+
+    #[test]
+    fn calver_released_since() {
+        let versions =
+            Versions::from(["2022.12.1", "20230930", "2023.10.1", "2023.2.0-rc1"].as_ref())
+                .scheme(VersionScheme::CalVer);
+        assert_eq!(
+            versions.clone().find_latest_versions(
+                &[
+                    Requirement::parse_calver("2023.9.*").unwrap(),
+                    Requirement::parse_calver("2022").unwrap(),
+                    Requirement::STAR
+                ],
+                false
+            ),
+            vec![
+                Some(String::from("20230930")),
+                Some(String::from("2022.12.1")),
+                Some(String::from("2023.10.1")),
+            ]
+        );
+        assert_eq!(
+            versions
+                .released_since("2023-01-01")
+                .find_latest_versions(&[Requirement::parse_calver("2022").unwrap()], false),
+            vec![None]
+        );
+
+        let release = |version: &str, published_at: Option<&str>| Release {
+            version: version.into(),
+            published_at: published_at.map(String::from),
+            status: ReleaseStatus::Published,
+        };
+        let versions = vec![
+            release("1.0.0", Some("2022-01-01T00:00:00Z")),
+            release("1.1.0", None),
+            release("0.9.0", Some("2023-06-01T00:00:00Z")),
+        ]
+        .into_iter()
+        .collect::<Versions>()
+        .released_since("2023-01-01");
+        assert_eq!(
+            versions.find_latest_versions(&[Requirement::STAR], false),
+            vec![Some(String::from("0.9.0"))]
+        );
+    }
+
+    #[test]
+    fn unparsed_versions() {
+        let versions = Versions::from(["r9", "r10", "r09b"].as_ref());
+        assert_eq!(versions.unparsed(), vec!["r9", "r10", "r09b"]);
+        assert_eq!(
+            versions.clone().find_latest(&[Requirement::STAR], false),
+            (vec![None], None)
+        );
+        assert_eq!(
+            versions.natural_fallback(true).find_latest(
+                &[Requirement::parse("^1").unwrap(), Requirement::STAR],
+                false
+            ),
+            (
+                vec![None, Some(String::from("r10"))],
+                Some(String::from("r10"))
+            )
+        );
+
+        let versions = Versions::from(["1.0.0", "latest"].as_ref()).natural_fallback(true);
+        assert_eq!(versions.unparsed(), vec!["latest"]);
+        assert_eq!(
+            versions.find_latest(&[Requirement::STAR], false),
+            (
+                vec![Some(String::from("1.0.0"))],
+                Some(String::from("1.0.0"))
+            )
+        );
+    }
+
+    #[test_case("r9", "r10", Ordering::Less; "numbers")]
+    #[test_case("r09", "r9", Ordering::Equal; "leading zeros")]
+    #[test_case("r9", "r9b", Ordering::Less; "prefix")]
+    #[test_case("release-b", "release-a", Ordering::Greater; "text")]
+    fn natural_ordering(lhs: &str, rhs: &str, expected: Ordering) {
+        assert_eq!(natural_cmp(lhs, rhs), expected);
+    }
+
+    #[test]
+    fn variant_lines() {
+        let versions = Versions::from(
+            [
+                "30.1-jre",
+                "30.1-android",
+                "31.1-android",
+                "31.1-jre",
+                "32.0.0-jre",
+                "32.1.0-rc1",
+            ]
+            .as_ref(),
+        );
+        assert_eq!(versions.variants(), vec!["android", "jre"]);
+        assert_eq!(
+            versions.find_latest_versions(
+                &[
+                    Requirement::parse("^31@jre").unwrap(),
+                    Requirement::parse("^31@android").unwrap(),
+                    Requirement::parse("*@android").unwrap(),
+                    Requirement::parse("*").unwrap(),
+                ],
+                false
+            ),
+            vec![
+                Some(String::from("31.1-jre")),
+                Some(String::from("31.1-android")),
+                Some(String::from("30.1-android")),
+                Some(String::from("32.0.0-jre")),
+            ]
+        );
+    }
+
+    #[test]
+    fn variant_order_does_not_matter() {
+        let versions = ["31.1-jre", "31.1-android", "30.1-jre"];
+        let requirements = [Requirement::parse("^31").unwrap()];
+        let forward = Versions::from(versions.as_ref());
+        let backward = versions.iter().rev().copied().collect::<Versions>();
+        assert_eq!(
+            forward.find_latest(&requirements, false),
+            (
+                vec![Some(String::from("31.1-android"))],
+                Some(String::from("31.1-android"))
+            )
+        );
+        assert_eq!(
+            backward.find_latest(&requirements, false),
+            (
+                vec![Some(String::from("31.1-android"))],
+                Some(String::from("31.1-android"))
+            )
+        );
+    }
+
+    #[test_case("31.1-jre", Some("jre"); "jre")]
+    #[test_case("5.6.0.Final", Some("Final"); "final release")]
+    #[test_case("5.6.0-Final", Some("Final"); "final release with dash")]
+    #[test_case("2.0.0.RELEASE", Some("RELEASE"); "release")]
+    #[test_case("1.1.GA", Some("GA"); "general availability")]
+    #[test_case("21.0.0-ea", None; "early access")]
+    #[test_case("1.0.0-experimental", None; "experimental")]
+    #[test_case("13.0.0-next", None; "next")]
+    #[test_case("1.0.0-pre", None; "pre")]
+    #[test_case("5.6.0.CR1", None; "candidate release")]
+    fn known_variants(version: &str, expected: Option<&str>) {
+        let version = VersionScheme::Semver.parse(version).unwrap();
+        assert_eq!(variant(&version), expected);
+    }
+
+    #[test]
+    fn overall_stays_on_the_variant_line() {
+        let versions = || Versions::from(["31.1-jre", "33.0.0-jre", "33.0.0-android"].as_ref());
+        assert_eq!(
+            versions().find_latest(&[Requirement::parse("^31@jre").unwrap()], false),
+            (
+                vec![Some(String::from("31.1-jre"))],
+                Some(String::from("33.0.0-jre"))
+            )
+        );
+        assert_eq!(
+            versions().find_latest(&[Requirement::STAR.with_variant(Some("jre".into()))], false),
+            (
+                vec![Some(String::from("33.0.0-jre"))],
+                Some(String::from("33.0.0-jre"))
+            )
+        );
+        assert_eq!(
+            versions()
+                .find_latest(
+                    &[
+                        Requirement::parse("^31@jre").unwrap(),
+                        Requirement::parse("^31@android").unwrap()
+                    ],
+                    false
+                )
+                .1,
+            Some(String::from("33.0.0-android"))
+        );
+    }
+
+    #[test]
+    fn pinned_variants_stay_on_their_line() {
+        let versions = || Versions::from(["31.0-jre", "31.1-jre", "31.1-android"].as_ref());
+
+        let check = crate::parse_coordinates("com.google.guava:guava:31.1-jre").unwrap();
+        assert_eq!(check.versions[0].variant(), Some("jre"));
+        assert_eq!(
+            versions().find_latest_versions(&check.versions, false),
+            vec![Some(String::from("31.1-jre"))]
+        );
+
+        let pom = crate::Manifest::parse(
+            crate::ManifestKind::Maven,
+            String::from(
+                "<project><dependencies><dependency><groupId>com.google.guava</groupId><artifactId>guava</artifactId><version>31.0-jre</version></dependency></dependencies></project>",
+            ),
+        )
+        .unwrap();
+        assert_eq!(
+            versions().find_latest_versions(&[pom.dependencies()[0].requirement.clone()], false),
+            vec![Some(String::from("31.1-jre"))]
+        );
+    }
+
+    #[test]
+    fn variants_in_build_metadata() {
+        let versions = Versions::from(["5.6.0.Final", "5.6.1.Final", "6.0.0.CR1"].as_ref());
+        assert_eq!(versions.variants(), vec!["Final"]);
+        assert_eq!(
+            versions.find_latest_versions(
+                &[
+                    Requirement::parse("^5@final").unwrap(),
+                    Requirement::parse("*").unwrap(),
+                ],
+                false
+            ),
+            vec![Some(String::from("5.6.1.Final")), None]
+        );
+    }
+
+    #[test]
+    fn unknown_tags_are_pre_releases() {
+        let versions = Versions::from(["1.0.0", "1.1.0-experimental", "1.1.0-ea"].as_ref());
+        assert_eq!(
+            versions.latest_versions(false, vec![]),
+            vec![(Requirement::STAR, Some(String::from("1.0.0")))]
+        );
+    }
+
+    #[test_case("rc.1", Some(PreReleaseChannel::Rc); "rc")]
+    #[test_case("alpha02", Some(PreReleaseChannel::Alpha); "alpha with number")]
+    #[test_case("SNAPSHOT", Some(PreReleaseChannel::Nightly); "snapshot")]
+    #[test_case("M3", Some(PreReleaseChannel::Milestone); "milestone")]
+    #[test_case("beta-2", Some(PreReleaseChannel::Beta); "beta with dash")]
+    #[test_case("jre", None; "unknown")]
+    fn channel_from_tag(tag: &str, expected: Option<PreReleaseChannel>) {
+        assert_eq!(PreReleaseChannel::from_tag(tag), expected);
+    }
+
+    #[test]
+    fn prerelease_channel() {
+        let versions = Versions::from(
+            [
+                "1.0.0",
+                "1.1.0-alpha02",
+                "1.1.0-rc.1",
+                "1.2.0-SNAPSHOT",
+                "1.2.0-beta.1",
+            ]
+            .as_ref(),
+        );
+        let find = |channel| {
+            versions
+                .clone()
+                .pre_release_channel(Some(channel))
+                .find_latest_versions(&[Requirement::parse("^1").unwrap()], true)
+        };
+        assert_eq!(
+            find(PreReleaseChannel::Rc),
+            vec![Some(String::from("1.1.0-rc.1"))]
+        );
+        assert_eq!(
+            find(PreReleaseChannel::Beta),
+            vec![Some(String::from("1.2.0-beta.1"))]
+        );
+        assert_eq!(
+            versions
+                .pre_release_channel(Some(PreReleaseChannel::Rc))
+                .find_latest_versions(&[Requirement::parse("^1").unwrap()], false),
+            vec![Some(String::from("1.0.0"))]
+        );
+    }
 }
@@ -0,0 +1,176 @@
+use crate::{latest_versions, report, Config};
+use color_eyre::eyre::Result;
+use latest_version::{Client, Requirement, VersionCheck};
+use std::{sync::Arc, time::Duration};
+use tokio::{process::Command, time::MissedTickBehavior};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Watch {
+    pub(crate) interval: Duration,
+    pub(crate) hook: Option<String>,
+}
+
+/// Re-runs all checks every `interval` and reports only the results that changed.
+///
+/// The first results are always reported. The hook is run once for every changed requirement.
+pub(crate) async fn run(
+    client: Client,
+    config: Config,
+    checks: Vec<VersionCheck>,
+    watch: Watch,
+) -> Result<()> {
+    let client = Arc::new(client);
+    let mut previous = vec![None::<Vec<(Requirement, Option<String>)>>; checks.len()];
+    let mut interval = tokio::time::interval(watch.interval);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        let _ = interval.tick().await;
+
+        let tasks = checks
+            .iter()
+            .cloned()
+            .map(|check| {
+                let client = Arc::clone(&client);
+                let config = config.clone();
+                tokio::spawn(async move { latest_versions(&client, &config, &check).await })
+            })
+            .collect::<Vec<_>>();
+
+        for ((task, check), previous) in tasks.into_iter().zip(&checks).zip(&mut previous) {
+            let (latest, overall) = match task.await? {
+                Ok(latest) => latest,
+                Err(e) => {
+                    eprintln!(
+                        "Could not check {}:{}, retrying in {:?}: {}",
+                        check.coordinates.system_slug(),
+                        check.coordinates.package_slug(),
+                        watch.interval,
+                        e
+                    );
+                    continue;
+                }
+            };
+
+            let changed = match previous.as_ref() {
+                Some(previous) => latest
+                    .iter()
+                    .zip(previous)
+                    .filter(|(latest, previous)| latest != previous)
+                    .map(|(latest, _)| latest.clone())
+                    .collect::<Vec<_>>(),
+                None => {
+                    report(check, &latest, overall.as_deref()).await?;
+                    *previous = Some(latest);
+                    continue;
+                }
+            };
+
+            if !changed.is_empty() {
+                report(check, &changed, overall.as_deref()).await?;
+                if let Some(hook) = watch.hook.as_deref() {
+                    for (req, version) in &changed {
+                        run_hook(hook, check, req, version.as_deref()).await?;
+                    }
+                }
+                *previous = Some(latest);
+            }
+        }
+    }
+}
+
+async fn run_hook(
+    hook: &str,
+    check: &VersionCheck,
+    req: &Requirement,
+    version: Option<&str>,
+) -> Result<()> {
+    let mut command = if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        let _ = command.arg("/C");
+        command
+    } else {
+        let mut command = Command::new("sh");
+        let _ = command.arg("-c");
+        command
+    };
+
+    let status = command
+        .arg(hook)
+        .env("LATEST_VERSION_SYSTEM", check.coordinates.system_slug())
+        .env("LATEST_VERSION_PACKAGE", &*check.coordinates.package_slug())
+        .env("LATEST_VERSION_REQUIREMENT", req.to_string())
+        .env("LATEST_VERSION_VERSION", version.unwrap_or_default())
+        .status()
+        .await?;
+
+    if !status.success() {
+        eprintln!("Hook `{}` failed with {}", hook, status);
+    }
+    Ok(())
+}
+
+const MAX_INTERVAL_SECS: u64 = 365 * 60 * 60 * 24;
+
+/// Parses an interval like `30s`, `5m`, `2h`, or `1d`. A number without a unit is in seconds.
+pub(crate) fn parse_interval(input: &str) -> Result<Duration, String> {
+    let input = input.trim();
+    let (amount, unit) = input.split_at(
+        input
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(input.len()),
+    );
+    let amount = amount
+        .parse::<u64>()
+        .map_err(|_| format!("Expected an interval like 30s, 5m, or 1h, got `{}`", input))?;
+    let unit_seconds = match unit.trim() {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 60 * 60 * 24,
+        unit => {
+            return Err(format!(
+                "Unknown interval unit `{}`, use s, m, h, or d",
+                unit
+            ))
+        }
+    };
+    // tokio panics when the next tick overflows the clock, so stay well below that
+    let seconds = amount
+        .checked_mul(unit_seconds)
+        .filter(|&seconds| seconds <= MAX_INTERVAL_SECS)
+        .ok_or_else(|| format!("The interval `{}` is too long, use at most 365d", input))?;
+    if seconds == 0 {
+        return Err(String::from("The interval must not be zero"));
+    }
+    Ok(Duration::from_secs(seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("30", 30; "no unit")]
+    #[test_case("30s", 30; "seconds")]
+    #[test_case("5m", 300; "minutes")]
+    #[test_case("2h", 7200; "hours")]
+    #[test_case("1d", 86400; "days")]
+    #[test_case(" 10 m ", 600; "with spaces")]
+    fn test_interval(input: &str, expected: u64) {
+        assert_eq!(parse_interval(input), Ok(Duration::from_secs(expected)));
+    }
+
+    #[test_case(""; "empty")]
+    #[test_case("m"; "no amount")]
+    #[test_case("0s"; "zero")]
+    #[test_case("-5m"; "negative")]
+    #[test_case("5w"; "unknown unit")]
+    #[test_case("1.5h"; "fraction")]
+    #[test_case("999999999999999999d"; "overflow")]
+    #[test_case("366d"; "longer than a year")]
+    #[test_case("99999999999999999999"; "too large")]
+    fn test_invalid_interval(input: &str) {
+        assert!(parse_interval(input).is_err());
+    }
+}
@@ -0,0 +1,210 @@
+use crate::{find_latest_versions, Config};
+use color_eyre::eyre::Result;
+use console::style;
+use latest_version::{Client, Dependency, Workspace, WorkspaceDependency};
+use std::{fmt::Write, sync::Arc};
+use tokio::io::{self, AsyncWriteExt};
+
+/// Checks every requirement of a workspace once and reports the outdated ones with the members that use them.
+pub(crate) async fn run(client: Client, config: Config, workspaces: Vec<Workspace>) -> Result<()> {
+    let client = Arc::new(client);
+    for workspace in workspaces {
+        let dependencies = workspace.dependencies();
+        let tasks = dependencies
+            .iter()
+            .map(|dep| {
+                let client = Arc::clone(&client);
+                let config = config.clone();
+                let dep = dep.dependency.clone();
+                tokio::spawn(async move { latest_versions(&client, &config, &dep).await })
+            })
+            .collect::<Vec<_>>();
+
+        let mut results = Vec::with_capacity(tasks.len());
+        for (task, dep) in tasks.into_iter().zip(&dependencies) {
+            // a failed lookup is reported for its requirement, the other requirements are still checked
+            let result = task.await?.map_err(|e| e.to_string());
+            results.push((dep, result));
+        }
+
+        let mut msg = String::with_capacity(256);
+        write_report(&mut msg, &workspace, &results);
+        io::stdout().write_all(msg.as_bytes()).await?;
+    }
+
+    Ok(())
+}
+
+async fn latest_versions(
+    client: &Client,
+    config: &Config,
+    dep: &Dependency,
+) -> Result<(Option<String>, Option<String>)> {
+    let coordinates = &dep.coordinates;
+    let versions = client.query_versions(coordinates).await?;
+    let (mut matching, latest) = find_latest_versions(
+        client,
+        config,
+        coordinates,
+        versions,
+        vec![dep.requirement.clone()],
+    )
+    .await?;
+    Ok((matching.pop().and_then(|(_, latest)| latest), latest))
+}
+
+/// The latest matching and the latest overall version of a requirement, or why they could not be found.
+type WorkspaceResult<'a, 'w> = (
+    &'a WorkspaceDependency<'w>,
+    Result<(Option<String>, Option<String>), String>,
+);
+
+fn write_report(msg: &mut String, workspace: &Workspace, results: &[WorkspaceResult<'_, '_>]) {
+    let path = workspace
+        .root()
+        .path()
+        .map_or_else(|| String::from("workspace"), |p| p.display().to_string());
+    let members = workspace.members().len();
+    let _ = writeln!(
+        msg,
+        "Workspace {} with {} {}",
+        style(&path).bold(),
+        members,
+        if members == 1 { "member" } else { "members" }
+    );
+
+    let mut up_to_date = 0;
+    for (dep, result) in results {
+        let dependency = dep.dependency;
+        let coordinates = &dependency.coordinates;
+        let (matching, latest) = match result {
+            Ok(result) => result,
+            Err(error) => {
+                let _ = writeln!(
+                    msg,
+                    "  {}:{} {}, {}: {}",
+                    style(coordinates.system_slug()).magenta(),
+                    style(coordinates.package_slug()).blue(),
+                    style(dependency.raw_requirement()).cyan(),
+                    style("lookup failed").red(),
+                    error
+                );
+                continue;
+            }
+        };
+        let outdated = latest
+            .as_deref()
+            .and_then(|latest| dependency.bumped_requirement(latest));
+        if outdated.is_none() {
+            up_to_date += 1;
+            continue;
+        }
+
+        let _ = writeln!(
+            msg,
+            "  {}:{} {}, latest matching: {}, latest: {}, used by {}",
+            style(coordinates.system_slug()).magenta(),
+            style(coordinates.package_slug()).blue(),
+            style(dependency.raw_requirement()).cyan(),
+            matching.as_deref().map_or_else(
+                || style("none").yellow().to_string(),
+                |v| style(v).green().to_string()
+            ),
+            style(latest.as_deref().unwrap_or_default()).yellow().bold(),
+            dep.members.join(", "),
+        );
+    }
+    let _ = writeln!(
+        msg,
+        "  {} {} up to date",
+        style(up_to_date).green(),
+        if up_to_date == 1 {
+            "requirement is"
+        } else {
+            "requirements are"
+        }
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::Fixture;
+
+    #[test]
+    fn outdated_requirements() {
+        console::set_colors_enabled(false);
+        let dir = Fixture::new();
+        dir.write(
+                "Cargo.toml",
+                "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.dependencies]\nserde = \"1.0.100\"\n",
+            )
+            .write(
+                "crates/app/Cargo.toml",
+                "[package]\nname = \"app\"\n\n[dependencies]\nserde = { workspace = true }\nsemver = \"1.0.14\"\n",
+            );
+
+        let workspace = Workspace::read(dir.join("Cargo.toml")).unwrap();
+        let dependencies = workspace.dependencies();
+        let serde = dependencies
+            .iter()
+            .find(|d| d.dependency.name == "serde")
+            .unwrap();
+        let semver = dependencies
+            .iter()
+            .find(|d| d.dependency.name == "semver")
+            .unwrap();
+        let results = [
+            (serde, Ok((Some("1.999.0".into()), Some("2.0.0".into())))),
+            (semver, Ok((Some("1.0.14".into()), Some("1.0.14".into())))),
+        ];
+
+        let mut msg = String::new();
+        write_report(&mut msg, &workspace, &results);
+        assert_eq!(
+            msg,
+            format!(
+                "Workspace {} with 1 member\n  cargo:serde 1.0.100, latest matching: 1.999.0, latest: 2.0.0, used by app\n  1 requirement is up to date\n",
+                dir.join("Cargo.toml").display()
+            )
+        );
+    }
+
+    #[test]
+    fn failed_lookup_is_reported_per_requirement() {
+        console::set_colors_enabled(false);
+        let dir = Fixture::new();
+        dir.write(
+                "Cargo.toml",
+                "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.dependencies]\nserde = \"1.0.100\"\n",
+            )
+            .write(
+                "crates/app/Cargo.toml",
+                "[package]\nname = \"app\"\n\n[dependencies]\nserde = { workspace = true }\nsemver = \"1.0.14\"\n",
+            )
+            .write(
+                "crates/cli/Cargo.toml",
+                "[package]\nname = \"cli\"\n\n[dependencies]\nserde = { workspace = true }\n",
+            );
+
+        let workspace = Workspace::read(dir.join("Cargo.toml")).unwrap();
+        let dependencies = workspace.dependencies();
+        let results = dependencies
+            .iter()
+            .map(|dep| match dep.dependency.name.as_str() {
+                "serde" => (dep, Err(String::from("connection refused"))),
+                _ => (dep, Ok((Some("1.0.14".into()), Some("1.0.14".into())))),
+            })
+            .collect::<Vec<_>>();
+
+        let mut msg = String::new();
+        write_report(&mut msg, &workspace, &results);
+        assert_eq!(
+            msg,
+            format!(
+                "Workspace {} with 2 members\n  cargo:serde 1.0.100, lookup failed: connection refused\n  1 requirement is up to date\n",
+                dir.join("Cargo.toml").display()
+            )
+        );
+    }
+}